@@ -1,43 +1,72 @@
-use bevy::ecs::prelude::*;
+use bevy::{ecs::system::SystemState, prelude::*};
 use bevy_sequential_actions::*;
 use derive_new::new;
 
-/// Pops up a temporary emote image sprite that hovers over the head of the
-/// entity that spawned it.
+use crate::prelude::*;
+
+/// Pops up a temporary emote that hovers over the head of the entity.
 #[derive(new)]
 pub struct EmoteAction {
     #[new(into)]
-    texture_name: String,
+    emote_name: String,
 
     #[new(value = "true")]
     blocking: bool,
-    // image_handle: Handle<Image>
+
+    #[new(default)]
+    started: bool,
 }
 
 impl EmoteAction {
-    pub fn non_blocking(texture_name: impl Into<String>) -> Self {
+    pub fn non_blocking(emote_name: impl Into<String>) -> Self {
         Self {
-            texture_name: texture_name.into(),
+            emote_name: emote_name.into(),
             blocking: false,
+            started: false,
         }
     }
 }
 
 impl Action for EmoteAction {
-    fn is_finished(&self, _agent: Entity, _world: &World) -> bool {
-        true
+    fn is_finished(&self, agent: Entity, world: &World) -> bool {
+        !self.blocking || world.get::<Emote>(agent).is_none()
     }
 
-    fn on_start(&mut self, _agent: Entity, _world: &mut World) -> bool {
-        // TODO: Implement this functionality later.
-        !self.blocking
+    fn on_start(&mut self, agent: Entity, world: &mut World) -> bool {
+        // Resuming after a pause keeps the emote that's already showing.
+        if !self.started {
+            let mut system_state: SystemState<(
+                Res<GameAssets>,
+                Res<Assets<EmoteConfig>>,
+            )> = SystemState::new(world);
+            let (game_assets, emote_configs) = system_state.get(world);
+            let emote = game_assets
+                .emotes
+                .get(format!("{}.emote", self.emote_name).as_str())
+                .and_then(|handle| emote_configs.get(handle))
+                .map(Emote::new);
+
+            match emote {
+                Some(emote) => {
+                    world.entity_mut(agent).insert(emote);
+                },
+                None => warn!("Missing emote `{}`", self.emote_name),
+            }
+
+            self.started = true;
+        }
+
+        self.is_finished(agent, world)
     }
 
     fn on_stop(
         &mut self,
         _agent: Entity,
         _world: &mut World,
-        _reason: StopReason,
+        reason: StopReason,
     ) {
+        if reason != StopReason::Paused {
+            self.started = false;
+        }
     }
 }
@@ -33,6 +33,14 @@ impl Plugin for ActionsPlugin {
             .add_systems(OnExit(GameState::Paused), resume_all_action_queues)
             .add_systems(OnEnter(GameState::GameOver), pause_all_action_queues)
             .add_systems(OnExit(GameState::GameOver), resume_all_action_queues)
+            .add_systems(
+                OnEnter(GameState::LevelComplete),
+                pause_all_action_queues,
+            )
+            .add_systems(
+                OnExit(GameState::LevelComplete),
+                resume_all_action_queues,
+            )
             .add_systems(
                 Update,
                 pause_added_action_queues.in_set(ActiveWhenPausedSet),
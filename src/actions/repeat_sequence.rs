@@ -9,15 +9,17 @@ use crate::prelude::*;
 /// **WARNING**: Doesn't work with
 /// [`RepeatAction`](crate::actions::RepeatAction).
 #[derive(new)]
-pub struct RepeatSequence<const N: usize> {
+pub struct RepeatSequence {
     repeat: Repeat,
-    actions: [BoxedAction; N],
+
+    #[new(into)]
+    actions: Vec<BoxedAction>,
 
     #[new(default)]
     index: usize,
 }
 
-impl<const N: usize> Action for RepeatSequence<N> {
+impl Action for RepeatSequence {
     fn is_finished(&self, agent: Entity, world: &World) -> bool {
         self.actions[self.index].is_finished(agent, world)
     }
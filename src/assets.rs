@@ -14,7 +14,8 @@ impl Plugin for AssetsPlugin {
         .configure_loading_state(
             LoadingStateConfig::new(GameState::AssetLoading)
                 .load_collection::<GameAssets>()
-                .init_resource::<PreloadedBlueprintAssets>(),
+                .init_resource::<PreloadedBlueprintAssets>()
+                .init_resource::<PreloadedLevelAssets>(),
         );
     }
 }
@@ -24,9 +25,15 @@ pub struct GameAssets {
     #[asset(path = "blueprints", collection(mapped, typed))]
     pub blueprints: HashMap<AssetFileStem, Handle<Blueprint>>,
 
+    #[asset(path = "main.campaign.ron")]
+    pub campaign: Handle<Campaign>,
+
     #[asset(path = "emotes", collection(mapped, typed))]
     pub emotes: HashMap<AssetFileStem, Handle<EmoteConfig>>,
 
+    #[asset(path = "levels", collection(mapped, typed))]
+    pub levels: HashMap<AssetFileStem, Handle<Level>>,
+
     #[asset(path = "sound_waves", collection(mapped, typed))]
     pub sound_waves: HashMap<AssetFileStem, Handle<SoundWaveConfig>>,
 }
@@ -0,0 +1,130 @@
+use bevy::{prelude::*, reflect::TypePath};
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a completed level lingers before moving on to the next one.
+const LEVEL_COMPLETE_DELAY: Duration = Duration::from_secs(2);
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Campaign>::new(&["campaign.ron"]))
+            .init_resource::<CurrentLevel>()
+            .add_systems(OnEnter(GameState::LevelTransition), start_level)
+            .add_systems(OnExit(GameState::LevelTransition), spawn_level)
+            .add_systems(OnEnter(GameState::LevelComplete), advance_level)
+            .add_systems(OnEnter(GameState::CampaignComplete), reset_campaign)
+            .add_systems(
+                Update,
+                wait_to_leave_level.run_if(in_state(GameState::LevelComplete)),
+            );
+    }
+}
+
+/// Asset listing the levels of the game in the order they're played.
+#[derive(Asset, Debug, Deserialize, Resource, TypePath)]
+pub struct Campaign {
+    /// Names of the level assets, like `"test.level"`.
+    pub levels: Vec<String>,
+}
+
+impl Campaign {
+    /// Checks that the campaign has levels and that they all exist.
+    pub fn validate(&self, game_assets: &GameAssets) -> Result<(), String> {
+        if self.levels.is_empty() {
+            return Err("campaign has no levels".to_owned());
+        }
+
+        if let Some(level_name) = self.levels.iter().find(|level_name| {
+            !game_assets.levels.contains_key(level_name.as_str())
+        }) {
+            return Err(format!("level `{level_name}` doesn't exist"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Index of the [`Campaign`] level being played.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct CurrentLevel(pub usize);
+
+/// Counts down until a completed level is left.
+#[derive(Debug, Resource)]
+struct LevelCompleteTimer(Timer);
+
+// The previous level's entities are torn down by their `ForStates` upon
+// entering the transition, so the next one spawns on the way out of it.
+fn start_level(
+    campaigns: Res<Assets<Campaign>>,
+    game_assets: Res<GameAssets>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    let campaign = campaigns.get(&game_assets.campaign).unwrap();
+
+    if let Err(message) = campaign.validate(&game_assets) {
+        error!("Invalid campaign: {message}");
+        next_game_state.set(GameState::InvalidCampaign);
+    } else {
+        next_game_state.set(GameState::Gameplay);
+    }
+}
+
+fn spawn_level(
+    mut commands: Commands,
+    campaigns: Res<Assets<Campaign>>,
+    game_assets: Res<GameAssets>,
+    current_level: Res<CurrentLevel>,
+) {
+    let campaign = campaigns.get(&game_assets.campaign).unwrap();
+
+    // Nothing to spawn when leaving for an invalid campaign.
+    let Some(level_name) = campaign.levels.get(current_level.0) else {
+        return;
+    };
+
+    commands.trigger(SpawnLevel::new(level_name.as_str()));
+    info!("Started level `{level_name}`");
+}
+
+fn advance_level(
+    mut commands: Commands,
+    mut current_level: ResMut<CurrentLevel>,
+) {
+    current_level.0 += 1;
+    commands.insert_resource(LevelCompleteTimer(Timer::new(
+        LEVEL_COMPLETE_DELAY,
+        TimerMode::Once,
+    )));
+}
+
+fn wait_to_leave_level(
+    time: Res<Time>,
+    mut level_complete_timer: ResMut<LevelCompleteTimer>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    current_level: Res<CurrentLevel>,
+    campaigns: Res<Assets<Campaign>>,
+    game_assets: Res<GameAssets>,
+) {
+    if !level_complete_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let campaign = campaigns.get(&game_assets.campaign).unwrap();
+
+    if current_level.0 < campaign.levels.len() {
+        next_game_state.set(GameState::LevelTransition);
+    } else {
+        next_game_state.set(GameState::CampaignComplete);
+    }
+}
+
+// Restarting from here plays the campaign again from the beginning.
+fn reset_campaign(mut current_level: ResMut<CurrentLevel>) {
+    current_level.0 = 0;
+    info!("Campaign complete");
+}
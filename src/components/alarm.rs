@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+pub(super) struct AlarmPlugin;
+
+impl Plugin for AlarmPlugin {
+    fn build(&self, app: &mut App) {
+        app.observe(sound_alarms);
+    }
+}
+
+/// Alerts guards within earshot to where the player was when a
+/// [`SecurityCamera`] spotted them.
+#[derive(Clone, Component, Debug)]
+pub struct Alarm {
+    /// How far the alarm can be heard in `meters`.
+    pub radius: f32,
+}
+
+/// Links [`SecurityCamera`] entities to the [`Alarm`] entities they set off.
+#[derive(Clone, Component, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AlarmChannel(pub u32);
+
+fn sound_alarms(
+    trigger: Trigger<RaiseAlarm>,
+    mut commands: Commands,
+    mut alarm_events: EventWriter<AlarmEvent>,
+    game_assets: Res<GameAssets>,
+    query: Query<(Entity, &GlobalTransform, &Alarm, Option<&AlarmChannel>)>,
+) {
+    let RaiseAlarm {
+        player_location,
+        channel,
+    } = *trigger.event();
+    let sound_wave = game_assets.sound_waves.get("alert.sound_wave").unwrap();
+
+    for (entity, transform, alarm, alarm_channel) in &query {
+        if channel.is_none() || alarm_channel != channel.as_ref() {
+            continue;
+        }
+
+        let origin = transform.translation();
+
+        alarm_events.send(AlarmEvent {
+            origin,
+            radius: alarm.radius,
+            player_location,
+        });
+        commands.trigger(
+            SpawnSoundWave::new(sound_wave.clone(), origin)
+                .with_emitter(entity),
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// How much a raised alarm adds to the [`AlertLevel`].
+const ALARM_ALERT: f32 = 0.5;
+
+/// How much a guard seeing the player adds to the [`AlertLevel`].
+const SIGHTING_ALERT: f32 = 0.2;
+
+/// How much shattered glass adds to the [`AlertLevel`].
+const SHATTER_ALERT: f32 = 0.15;
+
+/// How fast the [`AlertLevel`] calms down per `second`.
+const DECAY_RATE: f32 = 0.01;
+
+pub(super) struct AlertLevelPlugin;
+
+impl Plugin for AlertLevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AlertLevel>()
+            .add_systems(OnEnter(GameState::LevelTransition), reset_alert_level)
+            .add_systems(
+                Update,
+                (raise_alert_on_disturbances, decay_alert_level)
+                    .chain()
+                    .in_set(StoppedWhenPausedSet),
+            )
+            .observe(raise_alert_on_alarm);
+    }
+}
+
+/// How alert the guards are across the whole level, from `0.0` to `1.0`.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct AlertLevel(pub f32);
+
+impl AlertLevel {
+    pub fn tier(&self) -> AlertTier {
+        match self.0 {
+            x if x >= 0.7 => AlertTier::Alarmed,
+            x if x >= 0.3 => AlertTier::Wary,
+            _ => AlertTier::Calm,
+        }
+    }
+
+    /// Raises the alert level without going past the maximum.
+    pub fn raise(&mut self, amount: f32) {
+        self.0 = (self.0 + amount).min(1.0);
+    }
+}
+
+/// Thresholds of [`AlertLevel`] that guards and doors respond to.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd,
+)]
+pub enum AlertTier {
+    #[default]
+    Calm,
+    Wary,
+    Alarmed,
+}
+
+impl AlertTier {
+    /// Multiplier applied to how far guards can see.
+    pub fn vision_range_scale(&self) -> f32 {
+        match self {
+            Self::Calm => 1.0,
+            Self::Wary => 1.2,
+            Self::Alarmed => 1.5,
+        }
+    }
+}
+
+/// How a [`Door`] responds once the [`AlertLevel`] reaches a tier.
+#[derive(Clone, Component, Debug)]
+pub struct AlertResponse {
+    pub tier: AlertTier,
+    pub response: DoorResponse,
+}
+
+impl AlertResponse {
+    /// Whether the door should be forced into its response.
+    pub fn is_active(&self, alert_level: &AlertLevel) -> bool {
+        alert_level.tier() >= self.tier
+    }
+}
+
+/// What a [`Door`] does in response to the [`AlertLevel`].
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum DoorResponse {
+    /// Shuts and ignores its floor switches.
+    Lock,
+
+    /// Opens regardless of its floor switches.
+    Open,
+}
+
+fn raise_alert_on_alarm(
+    _trigger: Trigger<RaiseAlarm>,
+    mut alert_level: ResMut<AlertLevel>,
+) {
+    alert_level.raise(ALARM_ALERT);
+}
+
+fn raise_alert_on_disturbances(
+    mut alert_level: ResMut<AlertLevel>,
+    guard_query: Query<&Guard, Changed<Guard>>,
+    shattering_query: Query<(), Added<Shattering>>,
+) {
+    for guard in &guard_query {
+        if let Guard::SawPlayer(_) = guard {
+            alert_level.raise(SIGHTING_ALERT);
+        }
+    }
+
+    for _ in &shattering_query {
+        alert_level.raise(SHATTER_ALERT);
+    }
+}
+
+fn reset_alert_level(mut alert_level: ResMut<AlertLevel>) {
+    alert_level.0 = 0.0;
+}
+
+fn decay_alert_level(time: Res<Time>, mut alert_level: ResMut<AlertLevel>) {
+    if alert_level.0 > 0.0 {
+        alert_level.0 =
+            (alert_level.0 - DECAY_RATE * time.delta_seconds()).max(0.0);
+    }
+}
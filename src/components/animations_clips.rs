@@ -15,6 +15,14 @@ impl Plugin for AnimationClipsPlugin {
         .add_systems(OnExit(GameState::Paused), resume_all_animation_players)
         .add_systems(OnEnter(GameState::GameOver), pause_all_animation_players)
         .add_systems(OnExit(GameState::GameOver), resume_all_animation_players)
+        .add_systems(
+            OnEnter(GameState::LevelComplete),
+            pause_all_animation_players,
+        )
+        .add_systems(
+            OnExit(GameState::LevelComplete),
+            resume_all_animation_players,
+        )
         .add_systems(
             Update,
             (
@@ -45,15 +53,24 @@ pub struct AnimationGraphHandle(pub Handle<AnimationGraph>);
 #[derive(Clone, Component, Debug, Default)]
 pub struct AnimationClips(pub HashMap<String, AnimationNodeIndex>);
 
+/// Keeps an entity's animations playing while everything else is frozen.
+#[derive(Clone, Component, Debug, Default)]
+pub struct KeepsAnimating;
+
 /// Allows a parent entity to access the [`AnimationPlayer`] entity buried
 /// within its [`Scene`] hierarchy.
 #[derive(Component, Debug)]
 pub struct AnimationEntityLink(pub Entity);
 
 fn pause_all_animation_players(
-    mut animation_players: Query<&mut AnimationPlayer>,
+    mut animation_players: Query<(Entity, &mut AnimationPlayer)>,
+    kept_query: Query<&AnimationEntityLink, With<KeepsAnimating>>,
 ) {
-    for mut animation_player in &mut animation_players {
+    for (entity, mut animation_player) in &mut animation_players {
+        if kept_query.iter().any(|link| link.0 == entity) {
+            continue;
+        }
+
         animation_player.pause_all();
     }
 }
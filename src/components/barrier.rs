@@ -11,3 +11,7 @@ pub struct BlocksVision;
 /// A wall that can deflect sound waves.
 #[derive(Clone, Component, Debug, Default)]
 pub struct DeflectsSounds;
+
+/// Cuts a hole in the navmesh where the entity's collider is.
+#[derive(Clone, Component, Debug, Default)]
+pub struct NavMeshObstacle;
@@ -0,0 +1,141 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const MOVING_ANIMATION: &str = "moving";
+const IDLE_ANIMATION: &str = "idle";
+
+/// Speed in `meters/second` below which a character counts as standing still.
+const MOVING_SPEED_THRESHOLD: f32 = 0.1;
+
+pub(super) struct CharacterControllerPlugin;
+
+impl Plugin for CharacterControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                accelerate_characters,
+                turn_characters_toward_velocity,
+                animate_characters,
+            )
+                .chain()
+                .in_set(StoppedWhenPausedSet),
+        );
+    }
+}
+
+/// Required components for a [`CharacterController`] entity.
+#[derive(Bundle)]
+pub struct CharacterControllerBundle {
+    pub character_controller: CharacterController,
+    pub desired_velocity: DesiredVelocity,
+    pub movement_mode: MovementMode,
+}
+
+impl CharacterControllerBundle {
+    pub fn new(acceleration: f32, deceleration: f32) -> Self {
+        Self {
+            character_controller: CharacterController {
+                acceleration,
+                deceleration,
+                is_moving: false,
+            },
+            desired_velocity: DesiredVelocity::default(),
+            movement_mode: MovementMode::default(),
+        }
+    }
+}
+
+/// Moves a kinematic body directly by its velocity rather than by navigating
+/// to a [`Destination`].
+#[derive(Clone, Component, Debug)]
+pub struct CharacterController {
+    /// How quickly the character speeds up in `meters/second²`.
+    pub acceleration: f32,
+
+    /// How quickly the character slows down in `meters/second²`.
+    pub deceleration: f32,
+
+    is_moving: bool,
+}
+
+/// Velocity a [`CharacterController`] is trying to reach.
+#[derive(Clone, Component, Debug, Default)]
+pub struct DesiredVelocity(pub Vec3);
+
+fn accelerate_characters(
+    time: Res<Time>,
+    mut query: Query<(
+        &CharacterController,
+        &DesiredVelocity,
+        &mut LinearVelocity,
+    )>,
+) {
+    for (character_controller, desired_velocity, mut linear_velocity) in
+        &mut query
+    {
+        let rate = if desired_velocity.0.length_squared()
+            >= linear_velocity.0.length_squared()
+        {
+            character_controller.acceleration
+        } else {
+            character_controller.deceleration
+        };
+
+        linear_velocity.0 = linear_velocity
+            .0
+            .move_towards(desired_velocity.0, rate * time.delta_seconds());
+    }
+}
+
+fn turn_characters_toward_velocity(
+    time: Res<Time>,
+    mut query: Query<
+        (&mut Transform, &DesiredVelocity, &AngularSpeed),
+        With<CharacterController>,
+    >,
+) {
+    for (mut transform, desired_velocity, angular_speed) in &mut query {
+        // Keep facing the same way after letting go of the stick.
+        if desired_velocity.0 == Vec3::ZERO {
+            continue;
+        }
+
+        let yaw = transform.rotation.to_euler(EulerRot::YXZ).0;
+        let heading = Heading::from_vector(desired_velocity.0);
+        let diff = wrap_angle(heading.0 - yaw);
+        let delta = diff.signum() * angular_speed.0 * time.delta_seconds();
+        let yaw = if diff.abs() < delta.abs() {
+            heading.0
+        } else {
+            wrap_angle(yaw + delta)
+        };
+
+        transform.rotation = Quat::from_rotation_y(yaw).normalize();
+    }
+}
+
+fn animate_characters(
+    mut animations: Animations,
+    mut query: Query<(Entity, &mut CharacterController, &LinearVelocity)>,
+) {
+    for (entity, mut character_controller, linear_velocity) in &mut query {
+        let is_moving = linear_velocity.0.length() > MOVING_SPEED_THRESHOLD;
+
+        if is_moving == character_controller.is_moving {
+            continue;
+        }
+
+        character_controller.is_moving = is_moving;
+        animations.play_clip_name(
+            entity,
+            if is_moving {
+                MOVING_ANIMATION
+            } else {
+                IDLE_ANIMATION
+            },
+        );
+    }
+}
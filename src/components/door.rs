@@ -1,6 +1,24 @@
+use avian3d::prelude::Sensor;
 use bevy::{ecs::prelude::*, prelude::*};
 use bevy_sequential_actions::*;
 use seldom_state::prelude::*;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`Door`] takes to open or close.
+const DOOR_TRANSITION_DURATION: Duration = Duration::from_millis(500);
+
+/// Components a closed [`Door`] uses to block movement, vision, and sound.
+type DoorBlockers = (Barrier, BlocksVision, DeflectsSounds, NavMeshObstacle);
+
+pub(super) struct DoorPlugin;
+
+impl Plugin for DoorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, door_states.in_set(StoppedWhenPausedSet));
+    }
+}
 
 /// Required components for a [`Door`] entity.
 #[derive(Bundle)]
@@ -13,20 +31,34 @@ pub struct DoorBundle {
 
 impl Default for DoorBundle {
     fn default() -> Self {
-        // TODO: Use a state machine and actions to make it animate open and
-        // remove its Barrier, but then restore Barrier and close once
-        // signaled by floor switch.
+        use DoorState::*;
+        use DoorTransitionState::*;
 
         Self {
             door: Door,
             actions_bundle: ActionsBundle::new(),
-            state_machine: StateMachine::default(),
-            door_state: DoorState::Closed,
+            state_machine: StateMachine::default()
+                .trans_builder(should_open, |door_state, _| match door_state {
+                    Closed => Some(Opening),
+                    Open => None,
+                })
+                .trans_builder(should_close, |door_state, _| match door_state {
+                    Open => Some(Closing),
+                    Closed => None,
+                })
+                .trans_builder(done(None), |transition_state, _| {
+                    match transition_state {
+                        Opening => Some(Open),
+                        Closing => Some(Closed),
+                    }
+                }),
+            door_state: Closed,
         }
     }
 }
 
-/// A barrier that can be opened/closed.
+/// A barrier that can be opened/closed by [`FloorSwitch`] entities that share
+/// its [`SwitchChannel`], unless an [`AlertResponse`] overrides them.
 #[derive(Clone, Component, Debug, Default)]
 pub struct Door;
 
@@ -46,3 +78,108 @@ pub enum DoorTransitionState {
     Closing,
     Opening,
 }
+
+fn wants_open(
+    entity: Entity,
+    alert_level: &AlertLevel,
+    door_query: &Query<
+        (Option<&SwitchChannel>, Option<&AlertResponse>),
+        With<Door>,
+    >,
+    switch_query: &Query<(&SwitchChannel, &SwitchState)>,
+) -> bool {
+    let Ok((door_channel, alert_response)) = door_query.get(entity) else {
+        return false;
+    };
+
+    // Responding to the alert level overrides the floor switches.
+    if let Some(alert_response) = alert_response
+        .filter(|alert_response| alert_response.is_active(alert_level))
+    {
+        return alert_response.response == DoorResponse::Open;
+    }
+
+    let Some(door_channel) = door_channel else {
+        return false;
+    };
+
+    switch_query.iter().any(|(switch_channel, switch_state)| {
+        switch_channel.0 == door_channel.0
+            && matches!(switch_state, SwitchState::On)
+    })
+}
+
+fn should_open(
+    In(entity): In<Entity>,
+    alert_level: Res<AlertLevel>,
+    door_query: Query<
+        (Option<&SwitchChannel>, Option<&AlertResponse>),
+        With<Door>,
+    >,
+    switch_query: Query<(&SwitchChannel, &SwitchState)>,
+) -> bool {
+    wants_open(entity, &alert_level, &door_query, &switch_query)
+}
+
+fn should_close(
+    In(entity): In<Entity>,
+    alert_level: Res<AlertLevel>,
+    door_query: Query<
+        (Option<&SwitchChannel>, Option<&AlertResponse>),
+        With<Door>,
+    >,
+    switch_query: Query<(&SwitchChannel, &SwitchState)>,
+) -> bool {
+    !wants_open(entity, &alert_level, &door_query, &switch_query)
+}
+
+fn door_states(
+    mut commands: Commands,
+    door_state_query: Query<(Entity, &DoorState), Added<DoorState>>,
+    transition_state_query: Query<
+        (Entity, &DoorTransitionState),
+        Added<DoorTransitionState>,
+    >,
+) {
+    use DoorState::*;
+    use DoorTransitionState::*;
+
+    for (entity, door_state) in &door_state_query {
+        if let Open = door_state {
+            commands
+                .entity(entity)
+                .remove::<DoorBlockers>()
+                .insert((Sensor, Visibility::Hidden));
+        }
+    }
+
+    for (entity, transition_state) in &transition_state_query {
+        let clip_name = match transition_state {
+            Opening => "opening",
+            Closing => {
+                // Block everything immediately so nothing slips through while
+                // the door is closing.
+                commands.entity(entity).remove::<Sensor>().insert((
+                    Barrier,
+                    BlocksVision,
+                    DeflectsSounds,
+                    NavMeshObstacle,
+                    Visibility::Inherited,
+                ));
+                "closing"
+            },
+        };
+
+        commands.actions(entity).clear().add_many(actions![
+            ParallelActions::new(actions![
+                AnimationAction::non_blocking(clip_name),
+                SoundAction::new(clip_name),
+            ]),
+            WaitAction::new(DOOR_TRANSITION_DURATION),
+            |agent: Entity, world: &mut World| -> bool {
+                world.entity_mut(agent).insert(Done::Success);
+                true
+            },
+        ]);
+    }
+}
@@ -1,17 +1,116 @@
 use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_2, PI};
 
-/// Required components for a [`Emote`] entity.
-#[derive(Bundle)]
-pub struct EmoteBundle {
-    pub emote: Emote,
-    // TODO: Probably sprite bundle?
+use crate::prelude::*;
+
+/// Height above an entity's origin that its emote is drawn.
+const EMOTE_HEIGHT: f32 = 2.25;
+
+/// Height of an emote symbol in `meters`.
+const EMOTE_SIZE: f32 = 0.6;
+
+/// Number of segments used to draw the hook of a question mark.
+const HOOK_SEGMENTS: usize = 8;
+
+pub(super) struct EmotePlugin;
+
+impl Plugin for EmotePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                remove_expired_emotes.in_set(StoppedWhenPausedSet),
+                draw_emotes.in_set(PostAssetLoadingSet),
+            ),
+        );
+    }
 }
 
-/// An image that temporarily appears above and follows another entity.
+/// A symbol that temporarily appears above the head of an entity.
 #[derive(Clone, Component, Debug)]
 pub struct Emote {
-    source: Entity,
-    // TODO: Some kind of dissipation timer, or rely on particle system?
+    pub symbol: EmoteSymbol,
+    pub color: Color,
+    pub lifetime: Timer,
+}
+
+impl Emote {
+    pub fn new(config: &EmoteConfig) -> Self {
+        let color =
+            Srgba::hex(&config.color)
+                .map(Color::from)
+                .unwrap_or_else(|_| {
+                    warn!("Invalid emote color `{}`", config.color);
+                    Color::WHITE
+                });
+
+        Self {
+            symbol: config.symbol,
+            color,
+            lifetime: Timer::from_seconds(config.lifetime, TimerMode::Once),
+        }
+    }
 }
 
-// TODO: Need some kind of emote emitter or fixed height as a child entity?
+fn remove_expired_emotes(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Emote)>,
+) {
+    for (entity, mut emote) in &mut query {
+        if emote.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Emote>();
+        }
+    }
+}
+
+fn draw_emotes(
+    query: Query<(&GlobalTransform, &Emote)>,
+    camera_query: Query<&GlobalTransform, With<Camera3d>>,
+    mut gizmos: Gizmos,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    // Symbols are drawn flat against the screen.
+    let normal = camera_transform.back();
+    let right = camera_transform.right() * EMOTE_SIZE;
+    let up = camera_transform.up() * EMOTE_SIZE;
+    let dot_radius = EMOTE_SIZE * 0.08;
+
+    for (transform, emote) in &query {
+        let center = transform.translation() + Vec3::Y * EMOTE_HEIGHT;
+        let color = emote.color;
+
+        match emote.symbol {
+            EmoteSymbol::Exclamation => {
+                gizmos.line(center + up * 0.5, center - up * 0.2, color);
+                gizmos.circle(center - up * 0.4, normal, dot_radius, color);
+            },
+            EmoteSymbol::Question => {
+                let hook_center = center + up * 0.25;
+                let hook = (0..=HOOK_SEGMENTS).map(|i| {
+                    let angle =
+                        PI - (PI + FRAC_PI_2) * i as f32 / HOOK_SEGMENTS as f32;
+
+                    hook_center
+                        + (right * angle.cos() + up * angle.sin()) * 0.25
+                });
+
+                gizmos.linestrip(hook.chain([center - up * 0.2]), color);
+                gizmos.circle(center - up * 0.4, normal, dot_radius, color);
+            },
+            EmoteSymbol::Ellipsis => {
+                for offset in [-0.35, 0.0, 0.35] {
+                    gizmos.circle(
+                        center + right * offset,
+                        normal,
+                        dot_radius,
+                        color,
+                    );
+                }
+            },
+        }
+    }
+}
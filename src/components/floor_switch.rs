@@ -1,6 +1,20 @@
-use bevy::{ecs::prelude::*, prelude::*};
+use bevy::{ecs::prelude::*, math::Vec3Swizzles, prelude::*};
 use bevy_sequential_actions::*;
 use seldom_state::prelude::*;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`FloorSwitch`] takes to be pressed down or reset.
+const SWITCH_TRANSITION_DURATION: Duration = Duration::from_millis(250);
+
+pub(super) struct FloorSwitchPlugin;
+
+impl Plugin for FloorSwitchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, switch_states.in_set(StoppedWhenPausedSet));
+    }
+}
 
 /// Required components for a [`FloorSwitch`] entity.
 #[derive(Bundle)]
@@ -11,23 +25,53 @@ pub struct FloorSwitchBundle {
     pub switch_state: SwitchState,
 }
 
-impl Default for FloorSwitchBundle {
-    fn default() -> Self {
-        // TODO: Use a state machine and actions to make it animate, signal open
-        // doors, delay, and then signal doors to close them again.
+impl FloorSwitchBundle {
+    pub fn new(radius: f32, reset_delay: f32) -> Self {
+        use SwitchState::*;
+        use SwitchTransitionState::*;
 
         Self {
-            floor_switch: FloorSwitch,
+            floor_switch: FloorSwitch {
+                radius,
+                reset_delay: Duration::from_secs_f32(reset_delay),
+            },
             actions_bundle: ActionsBundle::new(),
-            state_machine: StateMachine::default(),
-            switch_state: SwitchState::Off,
+            state_machine: StateMachine::default()
+                .trans_builder(player_on_switch, |switch_state, _| {
+                    match switch_state {
+                        Off => Some(Pressing),
+                        On => None,
+                    }
+                })
+                .trans_builder(done(None), |switch_state, _| match switch_state
+                {
+                    On => Some(Resetting),
+                    Off => None,
+                })
+                .trans_builder(done(None), |transition_state, _| {
+                    match transition_state {
+                        Pressing => Some(On),
+                        Resetting => Some(Off),
+                    }
+                }),
+            switch_state: Off,
         }
     }
 }
 
 /// A switch on the floor that the player can step on to temporarily activate.
 #[derive(Clone, Component, Debug, Default)]
-pub struct FloorSwitch;
+pub struct FloorSwitch {
+    /// How close in `meters` the player must be to press the switch.
+    pub radius: f32,
+
+    /// How long the switch stays on before resetting.
+    pub reset_delay: Duration,
+}
+
+/// Links [`FloorSwitch`] entities to the [`Door`] entities they open.
+#[derive(Clone, Component, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SwitchChannel(pub u32);
 
 /// [`FloorSwitch`] current state.
 #[derive(Clone, Component, Copy, Default, Reflect)]
@@ -45,3 +89,72 @@ pub enum SwitchTransitionState {
     Pressing,
     Resetting,
 }
+
+fn player_on_switch(
+    In(entity): In<Entity>,
+    switch_query: Query<(&GlobalTransform, &FloorSwitch)>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) -> bool {
+    let Ok((transform, floor_switch)) = switch_query.get(entity) else {
+        return false;
+    };
+    let switch_location = transform.translation().xz();
+
+    player_query.iter().any(|player_transform| {
+        player_transform
+            .translation()
+            .xz()
+            .distance(switch_location)
+            <= floor_switch.radius
+    })
+}
+
+fn switch_states(
+    mut commands: Commands,
+    switch_state_query: Query<
+        (Entity, &FloorSwitch, &SwitchState),
+        Added<SwitchState>,
+    >,
+    transition_state_query: Query<
+        (Entity, &SwitchTransitionState),
+        Added<SwitchTransitionState>,
+    >,
+) {
+    use SwitchState::*;
+    use SwitchTransitionState::*;
+
+    for (entity, floor_switch, switch_state) in &switch_state_query {
+        let mut sequential_actions = commands.actions(entity);
+
+        sequential_actions.clear();
+
+        if let On = switch_state {
+            sequential_actions.add_many(actions![
+                WaitAction::new(floor_switch.reset_delay),
+                |agent: Entity, world: &mut World| -> bool {
+                    world.entity_mut(agent).insert(Done::Success);
+                    true
+                },
+            ]);
+        }
+    }
+
+    for (entity, transition_state) in &transition_state_query {
+        let clip_name = match transition_state {
+            Pressing => "pressing",
+            Resetting => "resetting",
+        };
+
+        commands.actions(entity).clear().add_many(actions![
+            ParallelActions::new(actions![
+                AnimationAction::non_blocking(clip_name),
+                SoundAction::new(clip_name),
+            ]),
+            WaitAction::new(SWITCH_TRANSITION_DURATION),
+            |agent: Entity, world: &mut World| -> bool {
+                world.entity_mut(agent).insert(Done::Success);
+                true
+            },
+        ]);
+    }
+}
@@ -1,12 +1,15 @@
-use bevy::prelude::*;
+use bevy::{math::Vec3Swizzles, prelude::*};
 
 use crate::prelude::*;
 
+/// How much [`QuietFootsteps`] shrinks the radius of footstep sound waves.
+const QUIET_FOOTSTEPS_RADIUS_SCALE: f32 = 0.2;
+
 pub(super) struct FootstepsPlugin;
 
 impl Plugin for FootstepsPlugin {
     fn build(&self, app: &mut App) {
-        // app.add_systems(Update, (ping, pong).chain().in_set(StopWhenPausedSet));
+        app.add_systems(Update, emit_footsteps.in_set(StoppedWhenPausedSet));
     }
 }
 
@@ -14,7 +17,7 @@ impl Plugin for FootstepsPlugin {
 #[derive(Bundle)]
 pub struct FootstepsBundle {
     pub footsteps: Footsteps,
-    // TODO: Probably sprite bundle?
+    pub walked_distance: WalkedDistance,
 }
 
 // Causes entity to emit sound-waves while translating, but not rotating.
@@ -22,7 +25,101 @@ pub struct FootstepsBundle {
 #[derive(Clone, Component, Debug)]
 pub struct Footsteps {
     pub sound_wave: Handle<SoundWaveConfig>,
-    // TODO: Some kind of dissipation timer, or rely on particle system?
+
+    /// Distance in `meters` traveled between each footstep.
+    pub stride: f32,
+}
+
+impl Footsteps {
+    /// Checks that the stride makes sense.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stride > 0.0 && self.stride.is_finite() {
+            Ok(())
+        } else {
+            Err(format!("stride must be positive, but is {}", self.stride))
+        }
+    }
+}
+
+/// How far an entity with [`Footsteps`] has walked since its last footstep.
+#[derive(Clone, Component, Debug, Default)]
+pub struct WalkedDistance {
+    previous_position: Option<Vec3>,
+    since_last_footstep: f32,
 }
 
-// TODO: Need some kind of footsteps emitter or fixed height as a child entity?
+fn emit_footsteps(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &GlobalTransform,
+        &Footsteps,
+        &mut WalkedDistance,
+        Option<&MovementMode>,
+        Has<QuietFootsteps>,
+    )>,
+) {
+    for (
+        entity,
+        transform,
+        footsteps,
+        mut walked_distance,
+        movement_mode,
+        is_quiet,
+    ) in &mut query
+    {
+        let position = transform.translation();
+        let previous_position = walked_distance
+            .previous_position
+            .replace(position)
+            .unwrap_or(position);
+
+        // Only translation counts, so turning in place is silent.
+        walked_distance.since_last_footstep +=
+            previous_position.xz().distance(position.xz());
+
+        if walked_distance.since_last_footstep < footsteps.stride {
+            continue;
+        }
+
+        walked_distance.since_last_footstep %= footsteps.stride;
+
+        let mut radius_scale =
+            movement_mode.map_or(1.0, MovementMode::footsteps_radius_scale);
+
+        if is_quiet {
+            radius_scale *= QUIET_FOOTSTEPS_RADIUS_SCALE;
+        }
+
+        commands.trigger(
+            SpawnSoundWave::new(footsteps.sound_wave.clone(), position)
+                .with_emitter(entity)
+                .with_radius_scale(radius_scale),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn footsteps(stride: f32) -> Footsteps {
+        Footsteps {
+            sound_wave: Handle::default(),
+            stride,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_positive_strides() {
+        assert!(footsteps(0.5).validate().is_ok());
+        assert!(footsteps(2.0).validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_strides() {
+        for stride in [0.0, -1.0, f32::INFINITY, f32::NAN] {
+            assert!(footsteps(stride).validate().is_err());
+        }
+    }
+}
@@ -1,6 +1,24 @@
+use avian3d::prelude::Sensor;
 use bevy::{ecs::prelude::*, prelude::*};
+use bevy_hanabi::prelude::*;
 use bevy_sequential_actions::*;
 use seldom_state::prelude::*;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`Glass`] wall takes to finish shattering.
+const SHATTER_DURATION: Duration = Duration::from_millis(750);
+
+pub(super) struct GlassPlugin;
+
+impl Plugin for GlassPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_glass_shards_effect)
+            .add_systems(Update, glass_states.in_set(StoppedWhenPausedSet))
+            .observe(crack_glass);
+    }
+}
 
 /// Required components for a [`Glass`] entity.
 #[derive(Bundle)]
@@ -13,14 +31,18 @@ pub struct GlassBundle {
 
 impl Default for GlassBundle {
     fn default() -> Self {
-        // TODO: Use a state machine and actions to remove its Barrier and spawn
-        // a particle effect.
+        use GlassState::*;
 
         Self {
             glass: Glass,
             actions_bundle: ActionsBundle::new(),
-            state_machine: StateMachine::default(),
-            glass_state: GlassState::Solid,
+            state_machine: StateMachine::default()
+                .trans_builder(cracked, |glass_state, _| match glass_state {
+                    Solid => Some(Shattering),
+                    Shattered => None,
+                })
+                .trans::<Shattering, _>(done(None), Shattered),
+            glass_state: Solid,
         }
     }
 }
@@ -42,3 +64,121 @@ pub enum GlassState {
 #[derive(Clone, Component, Copy, Reflect)]
 #[component(storage = "SparseSet")]
 pub struct Shattering;
+
+/// A [`Glass`] wall that was hit hard enough to start shattering.
+#[derive(Clone, Component, Debug, Default)]
+pub struct Cracked;
+
+/// Particle effect for the shards of a shattering [`Glass`] wall.
+#[derive(Resource)]
+pub struct GlassShardsEffect(pub Handle<EffectAsset>);
+
+fn setup_glass_shards_effect(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+) {
+    let mut color_gradient = Gradient::new();
+    color_gradient.add_key(0.0, Vec4::new(0.8, 0.9, 1.0, 1.0));
+    color_gradient.add_key(1.0, Vec4::new(0.8, 0.9, 1.0, 0.0));
+
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec2::splat(0.08));
+    size_gradient.add_key(1.0, Vec2::splat(0.02));
+
+    let writer = ExprWriter::new();
+    let init_position = SetPositionSphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        radius: writer.lit(0.5).expr(),
+        dimension: ShapeDimension::Volume,
+    };
+    let init_velocity = SetVelocitySphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        speed: writer.lit(3.0).uniform(writer.lit(6.0)).expr(),
+    };
+    let init_lifetime = SetAttributeModifier::new(
+        Attribute::LIFETIME,
+        writer.lit(0.5).uniform(writer.lit(1.0)).expr(),
+    );
+    let update_gravity = AccelModifier::new(writer.lit(Vec3::Y * -9.8).expr());
+    let effect = EffectAsset::new(
+        vec![256],
+        Spawner::once(128.0.into(), true),
+        writer.finish(),
+    )
+    .with_name("glass_shards")
+    .init(init_position)
+    .init(init_velocity)
+    .init(init_lifetime)
+    .update(update_gravity)
+    .render(ColorOverLifetimeModifier {
+        gradient: color_gradient,
+    })
+    .render(SizeOverLifetimeModifier {
+        gradient: size_gradient,
+        screen_space_size: false,
+    });
+
+    commands.insert_resource(GlassShardsEffect(effects.add(effect)));
+}
+
+fn crack_glass(
+    trigger: Trigger<Shatter>,
+    mut commands: Commands,
+    query: Query<(), (With<Glass>, With<GlassState>)>,
+) {
+    let entity = trigger.entity();
+
+    if query.contains(entity) {
+        commands.entity(entity).insert(Cracked);
+    }
+}
+
+fn cracked(In(entity): In<Entity>, query: Query<Has<Cracked>>) -> bool {
+    query.get(entity).unwrap()
+}
+
+fn glass_states(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    glass_shards_effect: Res<GlassShardsEffect>,
+    shattering_query: Query<(Entity, &GlobalTransform), Added<Shattering>>,
+    shattered_query: Query<(Entity, &GlassState), Added<GlassState>>,
+) {
+    for (entity, transform) in &shattering_query {
+        let origin = transform.translation();
+        let sound_wave =
+            game_assets.sound_waves.get("shatter.sound_wave").unwrap();
+
+        // Let everything through, including guards finding new paths.
+        commands
+            .entity(entity)
+            .remove::<(Cracked, Barrier, DeflectsSounds, NavMeshObstacle)>()
+            .insert(Sensor);
+        commands.spawn((
+            ForStates::new(GameState::IN_LEVEL),
+            ParticleEffectBundle {
+                effect: ParticleEffect::new(glass_shards_effect.0.clone()),
+                transform: Transform::from_translation(origin),
+                ..default()
+            },
+        ));
+        commands.trigger(
+            SpawnSoundWave::new(sound_wave.clone(), origin)
+                .with_emitter(entity),
+        );
+        commands.actions(entity).clear().add_many(actions![
+            SoundAction::new("shatter"),
+            WaitAction::new(SHATTER_DURATION),
+            |agent: Entity, world: &mut World| -> bool {
+                world.entity_mut(agent).insert(Done::Success);
+                true
+            },
+        ]);
+    }
+
+    for (entity, glass_state) in &shattered_query {
+        if let GlassState::Shattered = glass_state {
+            commands.entity(entity).insert(Visibility::Hidden);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use avian3d::prelude::CollidingEntities;
 use bevy::prelude::*;
 use bevy_sequential_actions::*;
 use rand::prelude::*;
@@ -12,9 +13,12 @@ impl Plugin for GuardPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (guard_states, trigger_game_over_on_player_collision)
+            (guard_states, respond_to_alert_tier)
+                .chain()
                 .in_set(StoppedWhenPausedSet),
-        );
+        )
+        .add_systems(Update, catch_player.in_set(GameplaySet))
+        .observe(stun_guard);
     }
 }
 
@@ -22,6 +26,8 @@ impl Plugin for GuardPlugin {
 #[derive(Bundle)]
 pub struct GuardBundle {
     pub guard: Guard,
+    pub suspicion: Suspicion,
+    pub movement_mode: MovementMode,
     pub actions_bundle: ActionsBundle,
     pub state_machine: StateMachine,
 }
@@ -32,30 +38,53 @@ impl GuardBundle {
 
         Self {
             guard: Guard::Guarding(starting_location),
+            suspicion: Suspicion::default(),
+            movement_mode: MovementMode::Walking,
             actions_bundle: ActionsBundle::new(),
             state_machine: StateMachine::default()
-                .trans::<AnyState, _>(stunned, Stunned)
-                .trans_builder(saw_player, |guard, player_location| match guard
-                {
-                    Guarding(_) | InvestigateNoise(_) | GoToAlarm(_) => {
-                        Some(SawPlayer(player_location))
-                    },
-                    LostPlayer => Some(ChasePlayer(player_location)),
-                    _ => None,
+                .trans_builder(stunned, |guard, _| match guard {
+                    Stunned => None,
+                    _ => Some(Stunned),
+                })
+                .trans_builder(alerted_to_player, |guard, player_location| {
+                    match guard {
+                        Guarding(_) | Curious(_) | Suspicious(_)
+                        | InvestigateNoise(_) | GoToAlarm(_) => {
+                            Some(SawPlayer(player_location))
+                        },
+                        LostPlayer => Some(ChasePlayer(player_location)),
+                        _ => None,
+                    }
                 })
                 .trans_builder(
-                    heard_noise,
-                    |guard, noise_direction| match guard {
-                        Guarding(_) | InvestigateNoise(_) | GoToAlarm(_)
-                        | LostPlayer => Some(InvestigateNoise(noise_direction)),
+                    suspicious_of_player,
+                    |guard, player_location| match guard {
+                        Guarding(_) | Curious(_) | InvestigateNoise(_) => {
+                            Some(Suspicious(player_location))
+                        },
+                        _ => None,
+                    },
+                )
+                .trans_builder(
+                    curious_about_player,
+                    |guard, player_location| match guard {
+                        Guarding(_) => Some(Curious(player_location)),
                         _ => None,
                     },
                 )
                 .trans_builder(
                     heard_alarm,
                     |guard, player_location| match guard {
-                        Guarding(_) | InvestigateNoise(_) => {
-                            Some(GoToAlarm(player_location))
+                        Guarding(_) | InvestigateNoise(_) | GoToAlarm(_)
+                        | LostPlayer => Some(GoToAlarm(player_location)),
+                        _ => None,
+                    },
+                )
+                .trans_builder(
+                    heard_noise,
+                    |guard, noise_direction| match guard {
+                        Guarding(_) | InvestigateNoise(_) | LostPlayer => {
+                            Some(InvestigateNoise(noise_direction))
                         },
                         _ => None,
                     },
@@ -70,6 +99,8 @@ impl GuardBundle {
 #[component(storage = "SparseSet")]
 pub enum Guard {
     Stunned,
+    Curious(Vec3),
+    Suspicious(Vec3),
     SawPlayer(Vec3),
     ChasePlayer(Vec3),
     LostPlayer,
@@ -82,91 +113,124 @@ pub enum Guard {
 #[derive(Clone, Component, Debug, Default)]
 pub struct Stunnable;
 
-/// A [`Guard`] that can see the player.
-#[derive(Clone, Component, Debug, Default)]
-pub struct Vision {
-    pub distance: f32,
-    pub fov: f32,
-}
-
-/// A [`Guard`] that can hear and respond to sound waves.
-#[derive(Clone, Component, Debug, Default)]
-pub struct Hearing {
-    pub radius: f32,
-}
-
-fn stunned(In(entity): In<Entity>, query: Query<Has<Stunnable>>) -> bool {
-    let is_stunnable = query.get(entity).unwrap();
-
-    false
-}
+/// A [`Guard`] that's currently stunned, leaving it unable to see or hear.
+#[derive(Clone, Component, Debug)]
+pub struct StunnedFor(pub Duration);
 
-fn saw_player(
-    In(entity): In<Entity>,
-    guard_query: Query<Has<Vision>>,
-    player_query: Query<&Transform, With<Target>>,
-) -> Option<Vec3> {
-    // TODO: Use parent observer to bubble event up from child component.
-    let has_vision = guard_query.get(entity).unwrap();
+fn stun_guard(
+    trigger: Trigger<Stun>,
+    mut commands: Commands,
+    query: Query<(), (With<Guard>, With<Stunnable>)>,
+) {
+    let entity = trigger.entity();
 
-    if !has_vision {
-        return None;
+    if query.contains(entity) {
+        commands
+            .entity(entity)
+            .insert(StunnedFor(trigger.event().duration));
     }
+}
 
-    let Ok(player_transform) = player_query.get_single() else {
-        return None;
-    };
-
-    Some(player_transform.translation)
+fn stunned(In(entity): In<Entity>, query: Query<Has<StunnedFor>>) -> bool {
+    query.get(entity).unwrap()
 }
 
 fn heard_alarm(
     In(entity): In<Entity>,
-    query: Query<Has<Hearing>>,
+    query: Query<Option<&HeardAlarm>>,
 ) -> Option<Vec3> {
-    let has_hearing = query.get(entity).unwrap();
+    let heard_alarm = query.get(entity).unwrap();
 
-    // TODO: Use parent observer to bubble event up from child component.
-
-    None
+    heard_alarm.map(|heard_alarm| heard_alarm.0)
 }
 
 fn heard_noise(
     In(entity): In<Entity>,
-    query: Query<Has<Hearing>>,
+    query: Query<(&Transform, Option<&HeardNoise>, Has<HeardAlarm>)>,
 ) -> Option<Dir3> {
-    let has_hearing = query.get(entity).unwrap();
+    let (transform, heard_noise, heard_alarm) = query.get(entity).unwrap();
+
+    // Alarms take priority over noises heard at the same time.
+    if heard_alarm {
+        return None;
+    }
 
-    // TODO: Use parent observer to bubble event up from child component.
+    let noise_offset = heard_noise?.0 - transform.translation;
 
-    None
+    Dir3::new(noise_offset.with_y(0.0)).ok()
 }
 
-fn trigger_game_over_on_player_collision(
+fn catch_player(
     mut commands: Commands,
-    guard_query: Query<&Transform, With<Guard>>,
-    player_query: Query<(Entity, &Transform), With<Target>>,
+    mut animations: Animations,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut player_caught_events: EventWriter<PlayerCaughtEvent>,
+    mut guard_query: Query<
+        (Entity, &mut Transform, &CollidingEntities),
+        (With<Guard>, Without<Player>, Without<StunnedFor>),
+    >,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    animation_clips_query: Query<&AnimationClips>,
 ) {
-    for transform in &guard_query {
-        // TODO: Actual Player entity will work with just .single().
-        let Ok((player_entity, player_transform)) = player_query.get_single()
-        else {
+    let Ok((player, player_transform)) = player_query.get_single() else {
+        return;
+    };
+
+    for (guard, mut transform, colliding_entities) in &mut guard_query {
+        if !colliding_entities.contains(&player) {
             continue;
-        };
+        }
+
+        // Freeze the guard facing the player in its caught animation.
+        let offset =
+            (player_transform.translation - transform.translation).with_y(0.0);
 
-        if transform.translation.distance(player_transform.translation) < 0.1 {
-            commands.entity(player_entity).despawn_recursive();
+        if let Ok(direction) = Dir3::new(offset) {
+            transform.look_to(-direction, Dir3::Y);
         }
+
+        commands.actions(guard).clear();
+
+        // Guards without a caught animation are frozen mid-stride instead.
+        if animation_clips_query
+            .get(guard)
+            .is_ok_and(|animation_clips| {
+                animation_clips.0.contains_key("caught")
+            })
+        {
+            commands.entity(guard).insert(KeepsAnimating);
+            animations.play_clip_name(guard, "caught");
+        }
+
+        player_caught_events.send(PlayerCaughtEvent { guard, player });
+        next_game_state.set(GameState::GameOver);
+        break;
     }
 }
 
 fn guard_states(
     mut commands: Commands,
-    query: Query<(Entity, &Transform, &Guard), Changed<Guard>>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Guard,
+            Option<&StunnedFor>,
+            Option<&Patrol>,
+        ),
+        Changed<Guard>,
+    >,
+    alert_level: Res<AlertLevel>,
 ) {
     use Guard::*;
 
-    for (entity, transform, guard) in &query {
+    let alert_tier = alert_level.tier();
+
+    for (entity, transform, guard, stunned_for, patrol) in &query {
+        commands
+            .entity(entity)
+            .insert(movement_mode(guard, alert_tier));
+
         let mut sequential_actions = commands.actions(entity);
 
         sequential_actions.clear();
@@ -178,10 +242,48 @@ fn guard_states(
                         AnimationAction::new("stun"),
                         SoundAction::new("stun"),
                     ]),
-                    WaitAction::new(Duration::from_secs(3)),
+                    WaitAction::new(
+                        stunned_for.map_or(Duration::ZERO, |s| s.0)
+                    ),
                     AnimationAction::new("unstun"),
                     |agent: Entity, world: &mut World| -> bool {
-                        world.entity_mut(agent).insert(Done::Success);
+                        world
+                            .entity_mut(agent)
+                            .remove::<StunnedFor>()
+                            .insert(Done::Success);
+                        true
+                    },
+                ]);
+            },
+            Curious(player_location) => {
+                let glance_direction = Dir3::new(
+                    (*player_location - transform.translation).with_y(0.0),
+                )
+                .unwrap_or(transform.back());
+
+                sequential_actions.add_many(actions![
+                    ParallelActions::new(actions![
+                        EmoteAction::new("curious"),
+                        FaceDirectionAction::new(glance_direction),
+                    ]),
+                    WaitAction::new(Duration::from_millis(1500)),
+                    |agent: Entity, world: &mut World| -> bool {
+                        world.entity_mut(agent).insert(Done::Failure);
+                        true
+                    },
+                ]);
+            },
+            Suspicious(player_location) => {
+                sequential_actions.add_many(actions![
+                    ParallelActions::new(actions![
+                        SoundAction::new("distracted"),
+                        EmoteAction::new("suspicious"),
+                    ]),
+                    MoveToAction::new(*player_location),
+                    AnimationAction::new("confused"),
+                    WaitAction::new(Duration::from_millis(1500)),
+                    |agent: Entity, world: &mut World| -> bool {
+                        world.entity_mut(agent).insert(Done::Failure);
                         true
                     },
                 ]);
@@ -293,19 +395,81 @@ fn guard_states(
                 );
             },
             Guarding(starting_location) => {
-                // TODO: Takes an optional level script at spawn time?
-                // If none is provided, use default that returns to starting location and facing direction?
-
-                sequential_actions.add_many(actions![
-                    MoveToAction::new(starting_location.translation),
-                    FaceDirectionAction::new(-starting_location.forward()),
-                    AnimationAction::new("idle"),
-                ]);
+                sequential_actions.add_many(guarding_actions(
+                    starting_location,
+                    patrol,
+                    alert_tier,
+                ));
             },
         }
     }
 }
 
-// TODO: Remove this later.
-#[derive(Component)]
-pub struct Target;
+fn respond_to_alert_tier(
+    mut commands: Commands,
+    alert_level: Res<AlertLevel>,
+    mut previous_alert_tier: Local<AlertTier>,
+    mut query: Query<(Entity, &Guard, Option<&Patrol>, Option<&mut Vision>)>,
+) {
+    let alert_tier = alert_level.tier();
+
+    if alert_tier == *previous_alert_tier {
+        return;
+    }
+
+    *previous_alert_tier = alert_tier;
+
+    for (entity, guard, patrol, vision) in &mut query {
+        if let Some(mut vision) = vision {
+            vision.range_scale = alert_tier.vision_range_scale();
+        }
+
+        // Guards busy with something else pick up the change once they go
+        // back to guarding.
+        let Guard::Guarding(starting_location) = guard else {
+            continue;
+        };
+
+        commands
+            .entity(entity)
+            .insert(movement_mode(guard, alert_tier));
+        commands.actions(entity).clear().add_many(guarding_actions(
+            starting_location,
+            patrol,
+            alert_tier,
+        ));
+    }
+}
+
+/// Guards hurry after the player, and while guarding once alarmed, but take it
+/// easy otherwise.
+fn movement_mode(guard: &Guard, alert_tier: AlertTier) -> MovementMode {
+    match guard {
+        Guard::ChasePlayer(_) | Guard::GoToAlarm(_) => MovementMode::Running,
+        Guard::Guarding(_) if alert_tier == AlertTier::Alarmed => {
+            MovementMode::Running
+        },
+        _ => MovementMode::Walking,
+    }
+}
+
+/// Walks the guard's [`Patrol`] for the current [`AlertTier`], or returns it
+/// to its starting location and facing direction without one.
+fn guarding_actions(
+    starting_location: &Transform,
+    patrol: Option<&Patrol>,
+    alert_tier: AlertTier,
+) -> Vec<BoxedAction> {
+    if let Some(patrol) =
+        patrol.filter(|patrol| !patrol.waypoints_for(alert_tier).is_empty())
+    {
+        return vec![Box::new(patrol.to_action(alert_tier))];
+    }
+
+    actions![
+        MoveToAction::new(starting_location.translation),
+        FaceDirectionAction::new(-starting_location.forward()),
+        AnimationAction::new("idle"),
+    ]
+    .into()
+}
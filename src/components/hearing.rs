@@ -0,0 +1,112 @@
+use bevy::{math::Vec3Swizzles, prelude::*};
+
+use crate::prelude::*;
+
+pub(super) struct HearingPlugin;
+
+impl Plugin for HearingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, listen_for_sounds.in_set(StoppedWhenPausedSet));
+    }
+}
+
+/// An entity that can hear and respond to sound waves and alarms.
+#[derive(Clone, Component, Debug)]
+pub struct Hearing {
+    /// Radius in `meters` that a sound must pass through to be heard.
+    pub radius: f32,
+}
+
+impl Default for Hearing {
+    fn default() -> Self {
+        Self { radius: 1.0 }
+    }
+}
+
+impl Hearing {
+    /// Checks that the hearing radius makes sense.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.radius > 0.0 && self.radius.is_finite() {
+            Ok(())
+        } else {
+            Err(format!("radius must be positive, but is {}", self.radius))
+        }
+    }
+}
+
+/// Where a noise heard during the current frame seemed to come from.
+#[derive(Clone, Component, Debug)]
+pub struct HeardNoise(pub Vec3);
+
+/// Where the player was when an alarm heard during the current frame went
+/// off.
+#[derive(Clone, Component, Debug)]
+pub struct HeardAlarm(pub Vec3);
+
+fn listen_for_sounds(
+    mut commands: Commands,
+    mut heard_noise_events: EventReader<HeardNoiseEvent>,
+    mut alarm_events: EventReader<AlarmEvent>,
+    heard_query: Query<Entity, Or<(With<HeardNoise>, With<HeardAlarm>)>>,
+    listener_query: Query<
+        (Entity, &GlobalTransform, &Hearing),
+        Without<StunnedFor>,
+    >,
+    guard_query: Query<(), With<Guard>>,
+) {
+    // Sounds only last for the frame they were heard in.
+    for entity in &heard_query {
+        commands.entity(entity).remove::<(HeardNoise, HeardAlarm)>();
+    }
+
+    for event in heard_noise_events.read() {
+        // Stunned listeners are deaf, and guards ignore noises made by other
+        // guards.
+        if !listener_query.contains(event.listener) {
+            continue;
+        }
+
+        if guard_query.contains(event.listener)
+            && event
+                .emitter
+                .is_some_and(|emitter| guard_query.contains(emitter))
+        {
+            continue;
+        }
+
+        if let Some(mut entity_commands) = commands.get_entity(event.listener) {
+            entity_commands.insert(HeardNoise(event.source));
+        }
+    }
+
+    for event in alarm_events.read() {
+        for (entity, transform, hearing) in &listener_query {
+            let distance =
+                transform.translation().xz().distance(event.origin.xz());
+
+            if distance <= event.radius + hearing.radius {
+                commands
+                    .entity(entity)
+                    .insert(HeardAlarm(event.player_location));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_accepts_positive_radii() {
+        assert!(Hearing::default().validate().is_ok());
+        assert!(Hearing { radius: 0.5 }.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_radii() {
+        for radius in [0.0, -1.0, f32::INFINITY, f32::NAN] {
+            assert!(Hearing { radius }.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use avian3d::prelude::CollidingEntities;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+pub(super) struct LevelExitPlugin;
+
+impl Plugin for LevelExitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, complete_level_at_exit.in_set(GameplaySet));
+    }
+}
+
+/// An area that completes the level once the player steps into it.
+#[derive(Clone, Component, Debug, Default)]
+pub struct LevelExit;
+
+fn complete_level_at_exit(
+    mut next_game_state: ResMut<NextState<GameState>>,
+    exit_query: Query<&CollidingEntities, With<LevelExit>>,
+    player_query: Query<(), With<Player>>,
+) {
+    let reached_exit = exit_query.iter().any(|colliding_entities| {
+        colliding_entities
+            .iter()
+            .any(|colliding_entity| player_query.contains(*colliding_entity))
+    });
+
+    if reached_exit {
+        next_game_state.set(GameState::LevelComplete);
+        info!("Level complete");
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::app::prelude::*;
 
+mod alarm;
+mod alert_level;
 mod animations_clips;
 mod barrier;
+mod character_controller;
 mod door;
 mod drop_shadow;
 mod emote;
@@ -10,16 +13,26 @@ mod footsteps;
 mod for_state;
 mod glass;
 mod guard;
+mod hearing;
+mod level_exit;
 mod movement;
+mod patrol;
 mod physics;
 mod pickup;
 mod player;
+mod projectile;
 mod security_camera;
+mod sound_wave;
 mod speed;
+mod suspicion;
+mod vision;
 mod weapon;
 
+pub use alarm::*;
+pub use alert_level::*;
 pub use animations_clips::*;
 pub use barrier::*;
+pub use character_controller::*;
 pub use door::*;
 pub use drop_shadow::*;
 pub use emote::*;
@@ -28,12 +41,19 @@ pub use footsteps::*;
 pub use for_state::*;
 pub use glass::*;
 pub use guard::*;
+pub use hearing::*;
+pub use level_exit::*;
 pub use movement::*;
+pub use patrol::*;
 pub use physics::*;
 pub use pickup::*;
 pub use player::*;
+pub use projectile::*;
 pub use security_camera::*;
+pub use sound_wave::*;
 pub use speed::*;
+pub use suspicion::*;
+pub use vision::*;
 pub use weapon::*;
 
 pub(super) struct ComponentsPlugin;
@@ -41,14 +61,31 @@ pub(super) struct ComponentsPlugin;
 impl Plugin for ComponentsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            AlarmPlugin,
+            AlertLevelPlugin,
             AnimationClipsPlugin,
+            CharacterControllerPlugin,
+            DoorPlugin,
+            EmotePlugin,
+            FloorSwitchPlugin,
             FootstepsPlugin,
             ForStatePlugin,
+            GlassPlugin,
+        ))
+        .add_plugins((
             GuardPlugin,
+            HearingPlugin,
+            LevelExitPlugin,
             MovementPlugin,
             PhysicsPlugin,
             PickupPlugin,
             PlayerPlugin,
+            ProjectilePlugin,
+            SecurityCameraPlugin,
+            SoundWavePlugin,
+            SuspicionPlugin,
+            VisionPlugin,
+            WeaponPlugin,
         ));
     }
 }
@@ -35,6 +35,35 @@ impl Plugin for MovementPlugin {
 #[derive(Clone, Component, Debug, Default)]
 pub struct Destination(pub Vec3);
 
+/// How an entity is moving, trading speed for stealth.
+#[derive(Clone, Component, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MovementMode {
+    #[default]
+    Walking,
+    Sneaking,
+    Running,
+}
+
+impl MovementMode {
+    /// Multiplier applied to the entity's [`LinearSpeed`].
+    pub fn speed_scale(&self) -> f32 {
+        match self {
+            Self::Walking => 1.0,
+            Self::Sneaking => 0.5,
+            Self::Running => 1.75,
+        }
+    }
+
+    /// Multiplier applied to the radius of the entity's [`Footsteps`].
+    pub fn footsteps_radius_scale(&self) -> f32 {
+        match self {
+            Self::Walking => 1.0,
+            Self::Sneaking => 0.3,
+            Self::Running => 2.0,
+        }
+    }
+}
+
 /// A direction that the entity will rotate to face.
 #[derive(Clone, Component, Debug)]
 pub struct Heading(pub f32);
@@ -185,6 +214,7 @@ fn follow_route_to_destination(
         &mut Heading,
         &LinearSpeed,
         &AngularSpeed,
+        Option<&MovementMode>,
     )>,
 ) {
     for (
@@ -195,8 +225,12 @@ fn follow_route_to_destination(
         mut heading,
         linear_speed,
         angular_speed,
+        movement_mode,
     ) in &mut query
     {
+        let speed = linear_speed.0
+            * movement_mode.map_or(1.0, MovementMode::speed_scale);
+
         // Translate toward next point on path.
         let diff = path_to.next - transform.translation;
         let dir = diff.normalize_or_zero();
@@ -207,7 +241,7 @@ fn follow_route_to_destination(
         transform.translation = if translation_finished {
             path_to.next
         } else {
-            transform.translation + dir * linear_speed.0 * time.delta_seconds()
+            transform.translation + dir * speed * time.delta_seconds()
         };
 
         // Rotate to face next point on path.
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use bevy_sequential_actions::*;
+use rand::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`Guard`] pauses on each glance while looking around.
+const LOOK_AROUND_DELAY: Duration = Duration::from_millis(1500);
+
+/// A route of waypoints that a [`Guard`] follows while guarding.
+#[derive(Clone, Component, Debug)]
+pub struct Patrol {
+    pub mode: PatrolMode,
+    pub waypoints: Vec<Waypoint>,
+
+    /// Route walked instead once the [`AlertLevel`] is alarmed, if any.
+    pub alerted_waypoints: Vec<Waypoint>,
+}
+
+impl Patrol {
+    /// Waypoints to walk for the current [`AlertTier`].
+    pub fn waypoints_for(&self, alert_tier: AlertTier) -> &[Waypoint] {
+        if alert_tier == AlertTier::Alarmed
+            && !self.alerted_waypoints.is_empty()
+        {
+            &self.alerted_waypoints
+        } else {
+            &self.waypoints
+        }
+    }
+
+    /// Builds an action that walks the patrol route for the current
+    /// [`AlertTier`].
+    pub fn to_action(&self, alert_tier: AlertTier) -> RepeatSequence {
+        let (repeat, steps) = self.plan(alert_tier);
+        let actions: Vec<BoxedAction> = steps
+            .into_iter()
+            .map(|step| -> BoxedAction {
+                match step {
+                    PatrolStep::MoveTo(position) => {
+                        Box::new(MoveToAction::new(position))
+                    },
+                    PatrolStep::Face(direction) => {
+                        Box::new(FaceDirectionAction::new(direction))
+                    },
+                    PatrolStep::Wait(duration) => {
+                        Box::new(WaitAction::new(duration))
+                    },
+                }
+            })
+            .collect();
+
+        RepeatSequence::new(repeat, actions)
+    }
+
+    /// Lays out the steps of the patrol route and how often they repeat.
+    fn plan(&self, alert_tier: AlertTier) -> (Repeat, Vec<PatrolStep>) {
+        let mut rng = SmallRng::from_entropy();
+        let waypoints = self.waypoints_for(alert_tier);
+        let mut route: Vec<&Waypoint> = waypoints.iter().collect();
+        let repeat = match self.mode {
+            PatrolMode::Once => Repeat::Times(1),
+            PatrolMode::Loop => Repeat::Forever,
+            PatrolMode::PingPong => {
+                // Walk back without visiting either end twice in a row.
+                let len = route.len();
+
+                if len > 2 {
+                    route.extend(waypoints[1..len - 1].iter().rev());
+                }
+
+                Repeat::Forever
+            },
+        };
+        let mut steps = vec![];
+
+        for waypoint in route {
+            steps.push(PatrolStep::MoveTo(waypoint.position));
+
+            if let Some(direction) =
+                waypoint.facing.and_then(|facing| Dir3::new(facing).ok())
+            {
+                steps.push(PatrolStep::Face(direction));
+            }
+
+            for _ in 0..waypoint.look_around {
+                let mut random_vector = Vec3::ZERO;
+                random_vector.x = rng.gen_range(-1.0..=1.0);
+                random_vector.z = rng.gen_range(-1.0..=1.0);
+
+                let random_direction =
+                    Dir3::new_unchecked(random_vector.normalize_or_zero());
+
+                steps.push(PatrolStep::Face(random_direction));
+                steps.push(PatrolStep::Wait(LOOK_AROUND_DELAY));
+            }
+
+            if waypoint.wait > 0.0 {
+                steps.push(PatrolStep::Wait(Duration::from_secs_f32(
+                    waypoint.wait,
+                )));
+            }
+        }
+
+        (repeat, steps)
+    }
+}
+
+/// A single step along a [`Patrol`] route.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PatrolStep {
+    MoveTo(Vec3),
+    Face(Dir3),
+    Wait(Duration),
+}
+
+/// How a [`Patrol`] continues after reaching its last waypoint.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub enum PatrolMode {
+    /// Stops at the last waypoint.
+    #[default]
+    Once,
+
+    /// Walks from the last waypoint back to the first one.
+    Loop,
+
+    /// Walks the waypoints in reverse order back to the first one.
+    PingPong,
+}
+
+/// A stop along a [`Patrol`] route.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Waypoint {
+    pub position: Vec3,
+
+    /// Direction to face after arriving.
+    #[serde(default)]
+    pub facing: Option<Vec3>,
+
+    /// Number of random directions to glance at after arriving.
+    #[serde(default)]
+    pub look_around: u32,
+
+    /// How long to wait in `seconds` before moving on.
+    #[serde(default)]
+    pub wait: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patrol(mode: PatrolMode) -> Patrol {
+        Patrol {
+            mode,
+            waypoints: vec![
+                Waypoint {
+                    position: Vec3::ZERO,
+                    facing: Some(Vec3::X),
+                    look_around: 0,
+                    wait: 0.0,
+                },
+                Waypoint {
+                    position: Vec3::X,
+                    facing: None,
+                    look_around: 1,
+                    wait: 2.0,
+                },
+                Waypoint {
+                    position: Vec3::Z,
+                    facing: None,
+                    look_around: 0,
+                    wait: 0.0,
+                },
+            ],
+            alerted_waypoints: vec![],
+        }
+    }
+
+    fn move_targets(steps: &[PatrolStep]) -> Vec<Vec3> {
+        steps
+            .iter()
+            .filter_map(|step| match step {
+                PatrolStep::MoveTo(position) => Some(*position),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn once_walks_every_step_a_single_time() {
+        let (mut repeat, steps) =
+            patrol(PatrolMode::Once).plan(AlertTier::Calm);
+
+        assert_eq!(steps.len(), 7);
+        assert_eq!(steps[0], PatrolStep::MoveTo(Vec3::ZERO));
+        assert_eq!(steps[1], PatrolStep::Face(Dir3::X));
+        assert_eq!(steps[2], PatrolStep::MoveTo(Vec3::X));
+        assert!(matches!(steps[3], PatrolStep::Face(_)));
+        assert_eq!(steps[4], PatrolStep::Wait(LOOK_AROUND_DELAY));
+        assert_eq!(steps[5], PatrolStep::Wait(Duration::from_secs(2)));
+        assert_eq!(steps[6], PatrolStep::MoveTo(Vec3::Z));
+
+        assert!(!repeat.is_finished());
+        repeat.advance();
+        assert!(repeat.is_finished());
+    }
+
+    #[test]
+    fn loop_repeats_the_route_forever() {
+        let (mut repeat, steps) =
+            patrol(PatrolMode::Loop).plan(AlertTier::Calm);
+
+        assert_eq!(move_targets(&steps), [Vec3::ZERO, Vec3::X, Vec3::Z]);
+
+        repeat.advance();
+        assert!(!repeat.is_finished());
+    }
+
+    #[test]
+    fn ping_pong_walks_back_without_repeating_the_ends() {
+        let (mut repeat, steps) =
+            patrol(PatrolMode::PingPong).plan(AlertTier::Calm);
+
+        assert_eq!(steps.len(), 11);
+        assert_eq!(
+            move_targets(&steps),
+            [Vec3::ZERO, Vec3::X, Vec3::Z, Vec3::X]
+        );
+
+        repeat.advance();
+        assert!(!repeat.is_finished());
+    }
+}
@@ -1,10 +1,22 @@
-use bevy::{app::prelude::*, ecs::prelude::*};
+use avian3d::prelude::*;
+use bevy::{app::prelude::*, ecs::prelude::*, prelude::*};
+
+use crate::prelude::*;
+
+/// Height of the capsule collider built from [`Physics`] in `meters`.
+const COLLIDER_HEIGHT: f32 = 1.0;
 
 pub(super) struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
-        // app.add_plugins((movement::MovementPlugin,));
+        app.add_systems(OnEnter(GameState::Paused), pause_physics)
+            .add_systems(OnExit(GameState::Paused), resume_physics)
+            .add_systems(OnEnter(GameState::GameOver), pause_physics)
+            .add_systems(OnExit(GameState::GameOver), resume_physics)
+            .add_systems(OnEnter(GameState::LevelComplete), pause_physics)
+            .add_systems(OnExit(GameState::LevelComplete), resume_physics)
+            .add_systems(PostProcessCollisions, separate_colliding_characters);
     }
 }
 
@@ -13,3 +25,91 @@ impl Plugin for PhysicsPlugin {
 pub struct Physics {
     pub radius: f32,
 }
+
+impl Physics {
+    /// Builds an upright capsule collider for an entity at the given scale, so
+    /// that its radius stays the same in world space.
+    pub fn collider(&self, scale: Vec3) -> Collider {
+        Collider::capsule(self.radius / scale.x, COLLIDER_HEIGHT / scale.y)
+    }
+}
+
+fn pause_physics(mut time: ResMut<Time<avian3d::prelude::Physics>>) {
+    time.pause();
+}
+
+fn resume_physics(mut time: ResMut<Time<avian3d::prelude::Physics>>) {
+    time.unpause();
+}
+
+/// Pushes characters out of walls and each other so they slide along them
+/// rather than passing through.
+fn separate_colliding_characters(
+    collisions: Res<Collisions>,
+    collider_parent_query: Query<&ColliderParent, Without<Sensor>>,
+    mut character_query: Query<
+        (&mut Position, &Rotation, &mut LinearVelocity),
+        (With<Physics>, Without<Sensor>),
+    >,
+) {
+    for contacts in collisions.iter() {
+        if !contacts.during_current_frame {
+            continue;
+        }
+
+        let Ok([parent1, parent2]) = collider_parent_query
+            .get_many([contacts.entity1, contacts.entity2])
+        else {
+            continue;
+        };
+        let (character1, character2) = (parent1.get(), parent2.get());
+        let is_character1 = character_query.contains(character1);
+        let is_character2 = character_query.contains(character2);
+
+        // Characters split the separation, while walls don't budge.
+        let share = if is_character1 && is_character2 {
+            0.5
+        } else {
+            1.0
+        };
+
+        for manifold in &contacts.manifolds {
+            let penetration = manifold
+                .contacts
+                .iter()
+                .map(|contact| contact.penetration)
+                .fold(0.0, f32::max);
+
+            if penetration <= 0.0 {
+                continue;
+            }
+
+            if let Ok((mut position, rotation, mut linear_velocity)) =
+                character_query.get_mut(character1)
+            {
+                let normal = -manifold.global_normal1(rotation).with_y(0.0);
+
+                position.0 += normal * penetration * share;
+                slide_along(&mut linear_velocity, normal);
+            }
+
+            if let Ok((mut position, rotation, mut linear_velocity)) =
+                character_query.get_mut(character2)
+            {
+                let normal = -manifold.global_normal2(rotation).with_y(0.0);
+
+                position.0 += normal * penetration * share;
+                slide_along(&mut linear_velocity, normal);
+            }
+        }
+    }
+}
+
+/// Removes the part of a velocity heading into a surface.
+fn slide_along(linear_velocity: &mut LinearVelocity, normal: Vec3) {
+    let into_surface = linear_velocity.0.dot(normal);
+
+    if into_surface < 0.0 {
+        linear_velocity.0 -= normal * into_surface;
+    }
+}
@@ -1,31 +1,127 @@
+use avian3d::prelude::CollidingEntities;
 use bevy::{ecs::prelude::*, math::prelude::*, prelude::*};
+use bevy_sequential_actions::*;
+use bevy_tweening::{lens::TransformScaleLens, Animator, EaseFunction, Tween};
+use seldom_state::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`Pickup`] takes to disappear or reappear.
+const PICKUP_TRANSITION_DURATION: Duration = Duration::from_millis(300);
 
 pub(super) struct PickupPlugin;
 
 impl Plugin for PickupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, spinning_rise_and_fall_effect);
+        app.add_systems(Update, spinning_rise_and_fall_effect)
+            .add_systems(
+                Update,
+                (pickup_states, grant_pickups)
+                    .chain()
+                    .in_set(StoppedWhenPausedSet),
+            );
     }
 }
 
 /// Required components for a [`Pickup`] entity.
-#[derive(Bundle, Clone, Debug)]
+#[derive(Bundle)]
 pub struct PickupBundle {
     pub pickup: Pickup,
+    pub actions_bundle: ActionsBundle,
+    pub state_machine: StateMachine,
+    pub pickup_state: PickupState,
 }
 
-impl Default for PickupBundle {
-    fn default() -> Self {
-        // TODO: Need states to represent it being visible, disappearing,
-        // disappeared, and reappearing after a set delay.
+impl PickupBundle {
+    pub fn new(kind: PickupKind, respawn_delay: Option<f32>) -> Self {
+        use PickupState::*;
+        use PickupTransitionState::*;
 
-        Self { pickup: Pickup }
+        Self {
+            pickup: Pickup {
+                kind,
+                respawn_delay: respawn_delay.map(Duration::from_secs_f32),
+            },
+            actions_bundle: ActionsBundle::new(),
+            state_machine: StateMachine::default()
+                .trans_builder(touched_by_player, |pickup_state, _| {
+                    match pickup_state {
+                        Visible => Some(Disappearing),
+                        Disappeared => None,
+                    }
+                })
+                .trans_builder(done(None), |pickup_state, _| match pickup_state
+                {
+                    Disappeared => Some(Reappearing),
+                    Visible => None,
+                })
+                .trans_builder(done(None), |transition_state, _| {
+                    match transition_state {
+                        Disappearing => Some(Disappeared),
+                        Reappearing => Some(Visible),
+                    }
+                }),
+            pickup_state: Visible,
+        }
     }
 }
 
 /// Items that player can pick up by colliding with them.
-#[derive(Clone, Component, Debug, Default)]
-pub struct Pickup;
+#[derive(Clone, Component, Debug)]
+pub struct Pickup {
+    pub kind: PickupKind,
+
+    /// How long the pickup stays gone before reappearing, if ever.
+    pub respawn_delay: Option<Duration>,
+}
+
+/// What a [`Pickup`] gives to the player who collects it.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum PickupKind {
+    InvisibilitySpray,
+    SilencerPads,
+    SoundGun,
+    ShockwaveCannon,
+    Briefcase,
+    BirthdayCake,
+}
+
+/// [`Pickup`] current state.
+#[derive(Clone, Component, Copy, Default, Reflect)]
+#[component(storage = "SparseSet")]
+pub enum PickupState {
+    #[default]
+    Visible,
+    Disappeared,
+}
+
+/// [`Pickup`] transition states.
+#[derive(Clone, Component, Copy, Reflect)]
+#[component(storage = "SparseSet")]
+pub enum PickupTransitionState {
+    Disappearing,
+    Reappearing,
+}
+
+/// Scale a [`Pickup`] returns to when it reappears.
+#[derive(Clone, Component, Debug)]
+struct RestingScale(Vec3);
+
+fn touched_by_player(
+    In(entity): In<Entity>,
+    pickup_query: Query<&CollidingEntities, With<Pickup>>,
+    player_query: Query<(), With<Player>>,
+) -> bool {
+    let Ok(colliding_entities) = pickup_query.get(entity) else {
+        return false;
+    };
+
+    colliding_entities
+        .iter()
+        .any(|colliding_entity| player_query.contains(*colliding_entity))
+}
 
 fn spinning_rise_and_fall_effect(
     time: Res<Time>,
@@ -46,3 +142,115 @@ fn spinning_rise_and_fall_effect(
         transform.translation.y = (rotation_angle).sin() * 0.1 + 0.2
     }
 }
+
+fn pickup_states(
+    mut commands: Commands,
+    mut pickup_collected_events: EventWriter<PickupCollectedEvent>,
+    player_query: Query<Entity, With<Player>>,
+    pickup_state_query: Query<
+        (Entity, &Pickup, &PickupState),
+        Added<PickupState>,
+    >,
+    transition_state_query: Query<
+        (
+            Entity,
+            &Pickup,
+            &Transform,
+            &PickupTransitionState,
+            Option<&RestingScale>,
+        ),
+        Added<PickupTransitionState>,
+    >,
+) {
+    use PickupState::*;
+    use PickupTransitionState::*;
+
+    for (entity, pickup, pickup_state) in &pickup_state_query {
+        let Disappeared = pickup_state else {
+            continue;
+        };
+        let Some(respawn_delay) = pickup.respawn_delay else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        commands.actions(entity).clear().add_many(actions![
+            WaitAction::new(respawn_delay),
+            |agent: Entity, world: &mut World| -> bool {
+                world.entity_mut(agent).insert(Done::Success);
+                true
+            },
+        ]);
+    }
+
+    for (entity, pickup, transform, transition_state, resting_scale) in
+        &transition_state_query
+    {
+        let resting_scale = resting_scale
+            .map_or(transform.scale, |resting_scale| resting_scale.0);
+        let (start, end) = match transition_state {
+            Disappearing => {
+                if let Ok(collector) = player_query.get_single() {
+                    pickup_collected_events.send(PickupCollectedEvent {
+                        pickup: entity,
+                        collector,
+                        kind: pickup.kind,
+                    });
+                }
+
+                (resting_scale, Vec3::ZERO)
+            },
+            Reappearing => (Vec3::ZERO, resting_scale),
+        };
+
+        commands.entity(entity).insert((
+            RestingScale(resting_scale),
+            Animator::new(Tween::new(
+                EaseFunction::QuadraticInOut,
+                PICKUP_TRANSITION_DURATION,
+                TransformScaleLens { start, end },
+            )),
+        ));
+        commands.actions(entity).clear().add_many(actions![
+            SoundAction::non_blocking("pickup"),
+            WaitAction::new(PICKUP_TRANSITION_DURATION),
+            |agent: Entity, world: &mut World| -> bool {
+                world.entity_mut(agent).insert(Done::Success);
+                true
+            },
+        ]);
+    }
+}
+
+fn grant_pickups(
+    mut commands: Commands,
+    mut pickup_collected_events: EventReader<PickupCollectedEvent>,
+) {
+    use PickupKind::*;
+
+    for event in pickup_collected_events.read() {
+        let weapon_blueprint = match event.kind {
+            InvisibilitySpray => {
+                commands
+                    .entity(event.collector)
+                    .add(apply_status_effect::<Invisibility>);
+                continue;
+            },
+            SilencerPads => {
+                commands
+                    .entity(event.collector)
+                    .add(apply_status_effect::<QuietFootsteps>);
+                continue;
+            },
+            SoundGun => "sound_gun.blueprint",
+            ShockwaveCannon => "shockwave_cannon.blueprint",
+            // Only matter to the level's objectives.
+            Briefcase | BirthdayCake => continue,
+        };
+
+        commands.trigger(
+            SpawnEntityFromBlueprint::new(weapon_blueprint, Mat4::IDENTITY)
+                .with_parent(event.collector),
+        );
+    }
+}
@@ -1,14 +1,35 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
+use std::time::Duration;
 
 use crate::prelude::*;
 
+/// How far the move stick has to tilt before the player stops sneaking.
+const SNEAK_TILT: f32 = 0.5;
+
+/// Opacity of the player's materials while they have [`Invisibility`].
+const INVISIBILITY_ALPHA: f32 = 0.3;
+
 pub(super) struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(InputManagerPlugin::<PlayerAction>::default())
-            .add_systems(Update, control_player.in_set(GameplaySet));
+            .add_systems(
+                Update,
+                (fade_invisible_players, restore_visible_players)
+                    .chain()
+                    .in_set(StoppedWhenPausedSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    control_player,
+                    wear_off_status_effect::<Invisibility>,
+                    wear_off_status_effect::<QuietFootsteps>,
+                )
+                    .in_set(GameplaySet),
+            );
     }
 }
 
@@ -34,21 +55,92 @@ impl Default for PlayerBundle {
 #[derive(Clone, Component, Debug, Default)]
 pub struct Player;
 
+/// A timed effect that removes itself from the player when it wears off.
+pub trait StatusEffect: Component + Sized {
+    /// How long a single application lasts.
+    const DURATION: Duration;
+
+    /// Most time that repeated applications can stack up to.
+    const MAX_DURATION: Duration;
+
+    fn from_timer(timer: Timer) -> Self;
+
+    fn timer_mut(&mut self) -> &mut Timer;
+}
+
 /// Blocks the Player from being seen by Vision.
-#[derive(Clone, Component, Debug, Default)]
-pub struct Invisibility; // timer: Timer
+#[derive(Clone, Component, Debug)]
+pub struct Invisibility {
+    timer: Timer,
+}
+
+impl StatusEffect for Invisibility {
+    const DURATION: Duration = Duration::from_secs(10);
+    const MAX_DURATION: Duration = Duration::from_secs(30);
+
+    fn from_timer(timer: Timer) -> Self {
+        Self { timer }
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
 
 /// Greatly reduces the emission radius of footstep sound waves.
-#[derive(Clone, Component, Debug, Default)]
-pub struct QuietFootsteps; // timer: Timer
+#[derive(Clone, Component, Debug)]
+pub struct QuietFootsteps {
+    timer: Timer,
+}
+
+impl StatusEffect for QuietFootsteps {
+    const DURATION: Duration = Duration::from_secs(15);
+    const MAX_DURATION: Duration = Duration::from_secs(45);
+
+    fn from_timer(timer: Timer) -> Self {
+        Self { timer }
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        &mut self.timer
+    }
+}
+
+/// Adds a [`StatusEffect`] to an entity, stacking its duration with any time
+/// left over from a previous application.
+pub fn apply_status_effect<T: StatusEffect>(entity: Entity, world: &mut World) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if let Some(mut status_effect) = entity_mut.get_mut::<T>() {
+        let timer = status_effect.timer_mut();
+        let duration = (timer.remaining() + T::DURATION).min(T::MAX_DURATION);
+
+        timer.set_duration(duration);
+        timer.reset();
+    } else {
+        entity_mut
+            .insert(T::from_timer(Timer::new(T::DURATION, TimerMode::Once)));
+    }
+}
+
+/// Player's original material from before it was faded by [`Invisibility`].
+#[derive(Clone, Component, Debug)]
+struct VisibleMaterial(Handle<StandardMaterial>);
 
 /// Allows the player to control their currently equipped firearm child entity.
 #[derive(Clone, Component, Debug)]
-pub struct EquippedFirearm(Entity);
+pub struct EquippedFirearm(pub Entity);
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Reflect)]
 pub enum PlayerAction {
     Move,
+    Aim,
+    Fire,
+    CycleWeapon,
+    Sneak,
+    Run,
 }
 
 impl PlayerAction {
@@ -59,7 +151,25 @@ impl PlayerAction {
             .with_dual_axis(Move, GamepadStick::LEFT)
             .with_dual_axis(Move, KeyboardVirtualDPad::ARROW_KEYS)
             .with_dual_axis(Move, KeyboardVirtualDPad::WASD)
-            .with_dual_axis(Move, KeyboardVirtualDPad::NUMPAD);
+            .with_dual_axis(Move, KeyboardVirtualDPad::NUMPAD)
+            .with_dual_axis(Aim, GamepadStick::RIGHT)
+            .with_dual_axis(
+                Aim,
+                KeyboardVirtualDPad::new(
+                    KeyCode::KeyI,
+                    KeyCode::KeyK,
+                    KeyCode::KeyJ,
+                    KeyCode::KeyL,
+                ),
+            )
+            .with(Fire, KeyCode::Space)
+            .with(Fire, GamepadButtonType::RightTrigger2)
+            .with(CycleWeapon, KeyCode::KeyQ)
+            .with(CycleWeapon, GamepadButtonType::North)
+            .with(Sneak, KeyCode::ControlLeft)
+            .with(Sneak, GamepadButtonType::LeftTrigger)
+            .with(Run, KeyCode::ShiftLeft)
+            .with(Run, GamepadButtonType::LeftTrigger2);
         input_map
     }
 }
@@ -67,30 +177,108 @@ impl PlayerAction {
 impl Actionlike for PlayerAction {
     fn input_control_kind(&self) -> InputControlKind {
         match self {
-            PlayerAction::Move => InputControlKind::DualAxis,
+            PlayerAction::Move | PlayerAction::Aim => {
+                InputControlKind::DualAxis
+            },
+            PlayerAction::Fire
+            | PlayerAction::CycleWeapon
+            | PlayerAction::Sneak
+            | PlayerAction::Run => InputControlKind::Button,
         }
     }
 }
 
 fn control_player(
-    mut commands: Commands,
-    time: Res<Time>,
-    query: Query<
-        (Entity, &Transform, &LinearSpeed, &ActionState<PlayerAction>),
+    mut query: Query<
+        (
+            &LinearSpeed,
+            &ActionState<PlayerAction>,
+            &mut DesiredVelocity,
+            &mut MovementMode,
+        ),
         With<Player>,
     >,
 ) {
-    let (entity, player_transform, linear_speed, action_state) = query.single();
-
-    let clamped_axis = action_state.clamped_axis_pair(&PlayerAction::Move).xy();
-    let move_direction =
-        Vec3::new(clamped_axis.x, 0.0, -clamped_axis.y).normalize_or_zero();
-
-    // Prevent snapping to facing forward on -Z when releasing stick.
-    if move_direction != Vec3::ZERO {
-        commands.entity(entity).insert(Destination(
-            player_transform.translation
-                + move_direction * linear_speed.0 * time.delta_seconds(),
-        ));
+    for (linear_speed, action_state, mut desired_velocity, mut movement_mode) in
+        &mut query
+    {
+        let clamped_axis =
+            action_state.clamped_axis_pair(&PlayerAction::Move).xy();
+        let move_direction =
+            Vec3::new(clamped_axis.x, 0.0, -clamped_axis.y).normalize_or_zero();
+
+        // Tilting the stick part way sneaks too, but standing still doesn't.
+        *movement_mode = if action_state.pressed(&PlayerAction::Sneak)
+            || (move_direction != Vec3::ZERO
+                && clamped_axis.length() < SNEAK_TILT)
+        {
+            MovementMode::Sneaking
+        } else if action_state.pressed(&PlayerAction::Run) {
+            MovementMode::Running
+        } else {
+            MovementMode::Walking
+        };
+        desired_velocity.0 =
+            move_direction * linear_speed.0 * movement_mode.speed_scale();
+    }
+}
+
+fn wear_off_status_effect<T: StatusEffect>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut T)>,
+) {
+    for (entity, mut status_effect) in &mut query {
+        if status_effect.timer_mut().tick(time.delta()).finished() {
+            commands.entity(entity).remove::<T>();
+        }
+    }
+}
+
+fn fade_invisible_players(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    player_query: Query<Entity, (With<Player>, Added<Invisibility>)>,
+    children_query: Query<&Children>,
+    material_query: Query<&Handle<StandardMaterial>, Without<VisibleMaterial>>,
+) {
+    for entity in &player_query {
+        for descendant in children_query.iter_descendants(entity) {
+            let Ok(material_handle) = material_query.get(descendant) else {
+                continue;
+            };
+            let Some(material) = materials.get(material_handle) else {
+                continue;
+            };
+            let mut faded_material = material.clone();
+
+            faded_material.base_color.set_alpha(INVISIBILITY_ALPHA);
+            faded_material.alpha_mode = AlphaMode::Blend;
+            commands.entity(descendant).insert((
+                VisibleMaterial(material_handle.clone()),
+                materials.add(faded_material),
+            ));
+        }
+    }
+}
+
+fn restore_visible_players(
+    mut commands: Commands,
+    mut removed_invisibility: RemovedComponents<Invisibility>,
+    children_query: Query<&Children>,
+    visible_material_query: Query<&VisibleMaterial>,
+) {
+    for entity in removed_invisibility.read() {
+        for descendant in children_query.iter_descendants(entity) {
+            let Ok(visible_material) = visible_material_query.get(descendant)
+            else {
+                continue;
+            };
+
+            commands
+                .entity(descendant)
+                .remove::<VisibleMaterial>()
+                .insert(visible_material.0.clone());
+        }
     }
 }
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Radius of the sphere drawn for a [`Projectile`].
+const DRAW_RADIUS: f32 = 0.1;
+
+/// Height above the ground that projectiles are drawn.
+const DRAW_HEIGHT: f32 = 0.5;
+
+pub(super) struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                move_projectiles.in_set(StoppedWhenPausedSet),
+                draw_projectiles.in_set(PostAssetLoadingSet),
+            ),
+        );
+    }
+}
+
+/// A shot that ricochets along a path and makes a sound where it lands.
+#[derive(Clone, Component, Debug)]
+pub struct Projectile {
+    /// Entity that fired the projectile.
+    pub shooter: Entity,
+
+    /// Points where the projectile starts, bounces, and lands.
+    pub path: Vec<Vec3>,
+
+    /// Travel speed in `meters/second`.
+    pub speed: f32,
+
+    /// How far along its path the projectile has traveled.
+    pub traveled_distance: f32,
+
+    /// Sound wave emitted where the projectile lands.
+    pub sound_wave: Handle<SoundWaveConfig>,
+}
+
+impl Projectile {
+    /// Where the projectile currently is along its path.
+    pub fn position(&self) -> Vec3 {
+        let mut remaining_distance = self.traveled_distance;
+
+        for segment in self.path.windows(2) {
+            let length = segment[0].distance(segment[1]);
+
+            if remaining_distance < length {
+                return segment[0]
+                    .lerp(segment[1], remaining_distance / length);
+            }
+
+            remaining_distance -= length;
+        }
+
+        self.landing_point()
+    }
+
+    /// Final point of the projectile's path.
+    pub fn landing_point(&self) -> Vec3 {
+        *self.path.last().unwrap()
+    }
+
+    /// Whether the projectile has reached the end of its path.
+    pub fn has_landed(&self) -> bool {
+        let length: f32 = self
+            .path
+            .windows(2)
+            .map(|segment| segment[0].distance(segment[1]))
+            .sum();
+
+        self.traveled_distance >= length
+    }
+}
+
+fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Projectile)>,
+) {
+    for (entity, mut projectile) in &mut query {
+        projectile.traveled_distance += projectile.speed * time.delta_seconds();
+
+        if !projectile.has_landed() {
+            continue;
+        }
+
+        // Credited to the shooter so it can't hear its own shot land.
+        commands.trigger(
+            SpawnSoundWave::new(
+                projectile.sound_wave.clone(),
+                projectile.landing_point(),
+            )
+            .with_emitter(projectile.shooter),
+        );
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn draw_projectiles(query: Query<&Projectile>, mut gizmos: Gizmos) {
+    for projectile in &query {
+        gizmos.sphere(
+            projectile.position().with_y(DRAW_HEIGHT),
+            Quat::IDENTITY,
+            DRAW_RADIUS,
+            Color::WHITE,
+        );
+    }
+}
@@ -1,6 +1,23 @@
 use bevy::{ecs::prelude::*, prelude::*};
 use bevy_sequential_actions::*;
 use seldom_state::prelude::*;
+use std::time::Duration;
+
+use crate::prelude::*;
+
+/// How long a [`SecurityCamera`] stays alarmed before it resumes sweeping.
+const ALARMED_DURATION: Duration = Duration::from_secs(5);
+
+pub(super) struct SecurityCameraPlugin;
+
+impl Plugin for SecurityCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            security_camera_states.in_set(StoppedWhenPausedSet),
+        );
+    }
+}
 
 /// Required components for a [`SecurityCamera`] entity.
 #[derive(Bundle)]
@@ -11,23 +28,60 @@ pub struct SecurityCameraBundle {
     pub security_camera_state: SecurityCameraState,
 }
 
-impl Default for SecurityCameraBundle {
-    fn default() -> Self {
-        // TODO: Use a state machine and actions to make it move back and forth.
+impl SecurityCameraBundle {
+    pub fn new(
+        starting_location: Transform,
+        sweep_angle: f32,
+        pause: f32,
+    ) -> Self {
+        use SecurityCameraState::*;
 
         Self {
-            security_camera: SecurityCamera,
+            security_camera: SecurityCamera {
+                center_heading: Heading::from_vector(*starting_location.back())
+                    .0,
+                sweep_angle,
+                pause: Duration::from_secs_f32(pause),
+            },
             actions_bundle: ActionsBundle::new(),
-            state_machine: StateMachine::default(),
-            security_camera_state: SecurityCameraState::PanningLeft,
+            state_machine: StateMachine::default()
+                .trans_builder(saw_player, |state, player_location| match state
+                {
+                    PanningLeft | PanningRight => {
+                        Some(Alarmed(player_location))
+                    },
+                    Alarmed(_) => None,
+                })
+                .trans_builder(done(None), |state, _| match state {
+                    PanningRight => Some(PanningLeft),
+                    PanningLeft | Alarmed(_) => Some(PanningRight),
+                }),
+            security_camera_state: PanningLeft,
         }
     }
 }
 
 /// Designates a security camera entity that can raise alarm events when it
 /// sees the player.
-#[derive(Clone, Component, Debug, Default)]
-pub struct SecurityCamera;
+#[derive(Clone, Component, Debug)]
+pub struct SecurityCamera {
+    /// [`Heading`] angle in the middle of the sweep.
+    pub center_heading: f32,
+
+    /// Full angle of the sweep in `radians`.
+    pub sweep_angle: f32,
+
+    /// How long the camera pauses at either end of its sweep.
+    pub pause: Duration,
+}
+
+impl SecurityCamera {
+    fn sweep_direction(&self, offset: f32) -> Dir3 {
+        let heading = wrap_angle(self.center_heading + offset);
+
+        Dir3::new_unchecked(Vec3::new(heading.sin(), 0.0, heading.cos()))
+    }
+}
 
 /// [`SecurityCamera`] current state.
 #[derive(Clone, Component, Copy, Default, Reflect)]
@@ -36,4 +90,67 @@ pub enum SecurityCameraState {
     #[default]
     PanningRight,
     PanningLeft,
+    Alarmed(Vec3),
+}
+
+fn security_camera_states(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &SecurityCamera,
+            &SecurityCameraState,
+            Option<&AlarmChannel>,
+        ),
+        Changed<SecurityCameraState>,
+    >,
+) {
+    use SecurityCameraState::*;
+
+    for (entity, security_camera, security_camera_state, alarm_channel) in
+        &query
+    {
+        let mut sequential_actions = commands.actions(entity);
+
+        sequential_actions.clear();
+
+        match security_camera_state {
+            PanningRight | PanningLeft => {
+                let half_sweep_angle = security_camera.sweep_angle * 0.5;
+                let offset = if matches!(security_camera_state, PanningLeft) {
+                    half_sweep_angle
+                } else {
+                    -half_sweep_angle
+                };
+
+                sequential_actions.add_many(actions![
+                    FaceDirectionAction::new(
+                        security_camera.sweep_direction(offset)
+                    ),
+                    WaitAction::new(security_camera.pause),
+                    |agent: Entity, world: &mut World| -> bool {
+                        world.entity_mut(agent).insert(Done::Success);
+                        true
+                    },
+                ]);
+            },
+            Alarmed(player_location) => {
+                commands.trigger(RaiseAlarm::new(
+                    *player_location,
+                    alarm_channel.copied(),
+                ));
+                commands.actions(entity).add_many(actions![
+                    ParallelActions::new(actions![
+                        SoundAction::new("alarm"),
+                        EmoteAction::new("alert"),
+                    ]),
+                    WaitAction::new(ALARMED_DURATION),
+                    |agent: Entity, world: &mut World| -> bool {
+                        world.entity_mut(agent).insert(Done::Success);
+                        true
+                    },
+                ]);
+            },
+        }
+    }
 }
@@ -0,0 +1,219 @@
+use bevy::{math::Vec3Swizzles, prelude::*};
+use std::f32::consts::TAU;
+
+use crate::prelude::*;
+
+/// Number of fronts that make up the ring of a [`SoundWave`].
+const FRONT_COUNT: usize = 64;
+
+/// Upper limit on bounces a single front can make in one frame.
+const MAX_BOUNCES_PER_STEP: usize = 4;
+
+/// Height above the ground that sound waves are drawn.
+const DRAW_HEIGHT: f32 = 0.2;
+
+pub(super) struct SoundWavePlugin;
+
+impl Plugin for SoundWavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (
+                    propagate_sound_waves,
+                    notify_listeners,
+                    despawn_expired_sound_waves,
+                )
+                    .chain()
+                    .in_set(StoppedWhenPausedSet),
+                draw_sound_waves.in_set(PostAssetLoadingSet),
+            ),
+        );
+    }
+}
+
+/// An expanding ring of sound that bounces off walls that deflect sounds and
+/// stops at other barriers.
+#[derive(Clone, Component, Debug)]
+pub struct SoundWave {
+    /// Entity responsible for making the sound, if any.
+    pub emitter: Option<Entity>,
+
+    /// Point the sound wave started expanding from.
+    pub origin: Vec3,
+
+    pub color: Color,
+
+    /// Expansion speed in `meters/second`.
+    pub speed: f32,
+
+    pub lifetime: Timer,
+
+    /// Segments of the ring, each traveling outward independently.
+    pub fronts: Vec<WaveFront>,
+
+    /// Entities with [`Hearing`] that this sound wave has already reached.
+    pub reached: Vec<Entity>,
+}
+
+impl SoundWave {
+    pub fn new(
+        config: &SoundWaveConfig,
+        origin: Vec3,
+        radius: f32,
+        emitter: Option<Entity>,
+    ) -> Self {
+        let fronts = (0..FRONT_COUNT)
+            .map(|i| {
+                let angle = TAU * i as f32 / FRONT_COUNT as f32;
+
+                WaveFront {
+                    position: origin,
+                    source: origin,
+                    direction: Dir3::new_unchecked(Vec3::new(
+                        angle.sin(),
+                        0.0,
+                        angle.cos(),
+                    )),
+                    remaining_distance: radius,
+                }
+            })
+            .collect();
+        let color =
+            Srgba::hex(&config.color)
+                .map(Color::from)
+                .unwrap_or_else(|_| {
+                    warn!("Invalid sound wave color `{}`", config.color);
+                    Color::WHITE
+                });
+
+        Self {
+            emitter,
+            origin,
+            color,
+            speed: config.speed,
+            lifetime: Timer::from_seconds(config.lifetime, TimerMode::Once),
+            fronts,
+            reached: vec![],
+        }
+    }
+}
+
+/// A single segment of a [`SoundWave`] ring.
+#[derive(Clone, Debug)]
+pub struct WaveFront {
+    pub position: Vec3,
+    pub direction: Dir3,
+
+    /// Where the front last started traveling in a straight line from, which
+    /// is either the origin or its latest bounce.
+    pub source: Vec3,
+
+    /// How much further the front can travel before it stops.
+    pub remaining_distance: f32,
+}
+
+impl WaveFront {
+    /// Whether the front is still traveling outward.
+    pub fn is_moving(&self) -> bool {
+        self.remaining_distance > 0.0
+    }
+}
+
+fn propagate_sound_waves(
+    time: Res<Time>,
+    sound_barriers: SoundBarriers,
+    mut query: Query<&mut SoundWave>,
+) {
+    for mut sound_wave in &mut query {
+        let step = sound_wave.speed * time.delta_seconds();
+
+        for front in sound_wave.fronts.iter_mut().filter(|f| f.is_moving()) {
+            let mut travel = step.min(front.remaining_distance);
+
+            front.remaining_distance -= travel;
+
+            for _ in 0..MAX_BOUNCES_PER_STEP {
+                let Some(hit) = sound_barriers.cast(
+                    front.position,
+                    front.direction,
+                    travel,
+                ) else {
+                    front.position += front.direction * travel;
+                    break;
+                };
+
+                front.position += front.direction * hit.distance;
+                travel -= hit.distance;
+
+                if !hit.deflects {
+                    front.remaining_distance = 0.0;
+                    break;
+                }
+
+                (front.position, front.direction) =
+                    hit.bounce(front.position, front.direction);
+                front.source = front.position;
+            }
+        }
+    }
+}
+
+fn notify_listeners(
+    mut sound_wave_query: Query<&mut SoundWave>,
+    listener_query: Query<(Entity, &GlobalTransform, &Hearing)>,
+    mut heard_noise_events: EventWriter<HeardNoiseEvent>,
+) {
+    for mut sound_wave in &mut sound_wave_query {
+        for (listener, transform, hearing) in &listener_query {
+            if sound_wave.emitter == Some(listener)
+                || sound_wave.reached.contains(&listener)
+            {
+                continue;
+            }
+
+            let location = transform.translation().xz();
+            let radius_squared = hearing.radius * hearing.radius;
+            let Some(front) = sound_wave.fronts.iter().find(|front| {
+                front.is_moving()
+                    && front.position.xz().distance_squared(location)
+                        <= radius_squared
+            }) else {
+                continue;
+            };
+
+            heard_noise_events.send(HeardNoiseEvent {
+                listener,
+                emitter: sound_wave.emitter,
+                origin: sound_wave.origin,
+                source: front.source,
+            });
+            sound_wave.reached.push(listener);
+        }
+    }
+}
+
+fn despawn_expired_sound_waves(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut SoundWave)>,
+) {
+    for (entity, mut sound_wave) in &mut query {
+        if sound_wave.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn draw_sound_waves(query: Query<&SoundWave>, mut gizmos: Gizmos) {
+    for sound_wave in &query {
+        let alpha = 1.0 - sound_wave.lifetime.fraction();
+        let points = sound_wave
+            .fronts
+            .iter()
+            .chain(sound_wave.fronts.first())
+            .map(|front| front.position.with_y(DRAW_HEIGHT));
+
+        gizmos.linestrip(points, sound_wave.color.with_alpha(alpha));
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::{math::Vec3Swizzles, pbr::light_consts, prelude::*};
+
+use crate::prelude::*;
+
+/// How fast [`Suspicion`] fills per `second` in ideal conditions.
+const FILL_RATE: f32 = 1.5;
+
+/// How fast [`Suspicion`] drains per `second` once the player is out of sight.
+const DECAY_RATE: f32 = 0.2;
+
+/// Least that distance or viewing angle can slow filling down to.
+const MIN_FILL_SCALE: f32 = 0.1;
+
+/// How visible the player is when no lights reach them.
+const MIN_ILLUMINATION: f32 = 0.3;
+
+/// Directional light illuminance in `lux` that fully lights up the player.
+const FULL_ILLUMINANCE: f32 = light_consts::lux::AMBIENT_DAYLIGHT;
+
+/// Height above the player's origin that lights are tested against.
+const LIT_HEIGHT: f32 = 0.5;
+
+/// How far toward a directional light to look for walls shading the player.
+const SHADE_DISTANCE: f32 = 100.0;
+
+/// Height above an entity's origin that its suspicion meter is drawn.
+const METER_HEIGHT: f32 = 1.5;
+
+/// Length of a full suspicion meter in `meters`.
+const METER_LENGTH: f32 = 0.75;
+
+pub(super) struct SuspicionPlugin;
+
+impl Plugin for SuspicionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                build_suspicion.in_set(StoppedWhenPausedSet),
+                draw_suspicion_meters.in_set(PostAssetLoadingSet),
+            ),
+        );
+    }
+}
+
+/// How aware an entity with [`Vision`] is of the player, from `0.0` to `1.0`.
+#[derive(Clone, Component, Debug, Default)]
+pub struct Suspicion(pub f32);
+
+impl Suspicion {
+    pub fn level(&self) -> SuspicionLevel {
+        match self.0 {
+            x if x >= 1.0 => SuspicionLevel::Alerted,
+            x if x >= 0.5 => SuspicionLevel::Suspicious,
+            x if x >= 0.25 => SuspicionLevel::Curious,
+            _ => SuspicionLevel::Calm,
+        }
+    }
+}
+
+/// Thresholds of [`Suspicion`] that entities react to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SuspicionLevel {
+    Calm,
+    Curious,
+    Suspicious,
+    Alerted,
+}
+
+impl SuspicionLevel {
+    fn color(&self) -> Color {
+        match self {
+            Self::Calm => Color::WHITE,
+            Self::Curious => Color::srgb(1.0, 1.0, 0.0),
+            Self::Suspicious => Color::srgb(1.0, 0.5, 0.0),
+            Self::Alerted => Color::srgb(1.0, 0.0, 0.0),
+        }
+    }
+}
+
+/// State machine trigger for when an entity becomes curious about the player.
+pub fn curious_about_player(
+    In(entity): In<Entity>,
+    query: Query<(&Suspicion, Option<&SeesPlayer>)>,
+) -> Option<Vec3> {
+    seen_at_level(entity, &query, SuspicionLevel::Curious)
+}
+
+/// State machine trigger for when an entity becomes suspicious of the player.
+pub fn suspicious_of_player(
+    In(entity): In<Entity>,
+    query: Query<(&Suspicion, Option<&SeesPlayer>)>,
+) -> Option<Vec3> {
+    seen_at_level(entity, &query, SuspicionLevel::Suspicious)
+}
+
+/// State machine trigger for when an entity is fully alerted to the player.
+pub fn alerted_to_player(
+    In(entity): In<Entity>,
+    query: Query<(&Suspicion, Option<&SeesPlayer>)>,
+) -> Option<Vec3> {
+    seen_at_level(entity, &query, SuspicionLevel::Alerted)
+}
+
+/// Where the player is if they're seen while at a [`SuspicionLevel`].
+fn seen_at_level(
+    entity: Entity,
+    query: &Query<(&Suspicion, Option<&SeesPlayer>)>,
+    level: SuspicionLevel,
+) -> Option<Vec3> {
+    let (suspicion, sees_player) = query.get(entity).unwrap();
+
+    if suspicion.level() != level {
+        return None;
+    }
+
+    sees_player.map(|sees_player| sees_player.0)
+}
+
+fn build_suspicion(
+    time: Res<Time>,
+    mut viewer_query: Query<(
+        &GlobalTransform,
+        &Vision,
+        &mut Suspicion,
+        Option<&SeesPlayer>,
+    )>,
+    player_query: Query<Option<&MovementMode>, With<Player>>,
+    line_of_sight: LineOfSight,
+    directional_light_query: Query<(&GlobalTransform, &DirectionalLight)>,
+    point_light_query: Query<(&GlobalTransform, &PointLight)>,
+    spot_light_query: Query<(&GlobalTransform, &SpotLight)>,
+) {
+    let movement_scale = match player_query.get_single() {
+        Ok(Some(MovementMode::Sneaking)) => 0.5,
+        Ok(Some(MovementMode::Running)) => 1.5,
+        _ => 1.0,
+    };
+
+    for (transform, vision, mut suspicion, sees_player) in &mut viewer_query {
+        let Some(SeesPlayer(player_location)) = sees_player else {
+            suspicion.0 =
+                (suspicion.0 - DECAY_RATE * time.delta_seconds()).max(0.0);
+            continue;
+        };
+
+        // Harder to notice the player far away or out of the corner of the
+        // eye.
+        let diff = (*player_location - transform.translation()).xz();
+        let max_distance = vision.distance.max(vision.peripheral_distance)
+            * vision.range_scale;
+        let half_fov = vision.fov.max(vision.peripheral_fov) * 0.5;
+        let closeness = 1.0 - diff.length() / max_distance;
+        let centrality = 1.0
+            - transform
+                .back()
+                .xz()
+                .angle_between(diff)
+                .abs()
+                .min(half_fov)
+                / half_fov;
+
+        // Darker spots make the player harder to notice, and lights don't
+        // reach through walls.
+        let lit_point = *player_location + Vec3::Y * LIT_HEIGHT;
+        let reaches_player = |light_location: Vec3| {
+            line_of_sight.is_clear(light_location, lit_point)
+        };
+        let directional_illumination = directional_light_query
+            .iter()
+            .filter(|(light_transform, _)| {
+                reaches_player(
+                    lit_point + light_transform.back() * SHADE_DISTANCE,
+                )
+            })
+            .map(|(_, light)| light.illuminance / FULL_ILLUMINANCE);
+        let point_illumination = point_light_query
+            .iter()
+            .filter(|(light_transform, _)| {
+                reaches_player(light_transform.translation())
+            })
+            .map(|(light_transform, light)| {
+                1.0 - light_transform.translation().distance(lit_point)
+                    / light.range
+            });
+        let spot_illumination = spot_light_query
+            .iter()
+            .filter(|(light_transform, light)| {
+                let diff = lit_point - light_transform.translation();
+
+                light_transform.forward().angle_between(diff)
+                    <= light.outer_angle
+                    && reaches_player(light_transform.translation())
+            })
+            .map(|(light_transform, light)| {
+                1.0 - light_transform.translation().distance(lit_point)
+                    / light.range
+            });
+        let illumination = directional_illumination
+            .chain(point_illumination)
+            .chain(spot_illumination)
+            .fold(MIN_ILLUMINATION, f32::max)
+            .min(1.0);
+        let fill_rate = FILL_RATE
+            * closeness.max(MIN_FILL_SCALE)
+            * centrality.max(MIN_FILL_SCALE)
+            * illumination
+            * movement_scale;
+
+        suspicion.0 = (suspicion.0 + fill_rate * time.delta_seconds()).min(1.0);
+    }
+}
+
+fn draw_suspicion_meters(
+    query: Query<(&GlobalTransform, &Suspicion)>,
+    mut gizmos: Gizmos,
+) {
+    for (transform, suspicion) in &query {
+        if suspicion.0 <= 0.0 {
+            continue;
+        }
+
+        let start = transform.translation()
+            + Vec3::new(-METER_LENGTH * 0.5, METER_HEIGHT, 0.0);
+
+        gizmos.line(
+            start,
+            start + Vec3::X * METER_LENGTH * suspicion.0,
+            suspicion.level().color(),
+        );
+    }
+}
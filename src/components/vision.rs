@@ -0,0 +1,273 @@
+use bevy::{math::Vec3Swizzles, prelude::*};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::prelude::*;
+
+/// Height above an entity's origin that sight lines are tested from.
+const EYE_HEIGHT: f32 = 0.5;
+
+pub(super) struct VisionPlugin;
+
+impl Plugin for VisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, look_for_player.in_set(StoppedWhenPausedSet));
+    }
+}
+
+/// An entity that can see the player within a cone in front of it, plus an
+/// optional wider but shorter peripheral cone.
+#[derive(Clone, Component, Debug)]
+pub struct Vision {
+    /// Maximum viewing distance in `meters`.
+    pub distance: f32,
+
+    /// Full angle of the vision cone in `radians`.
+    pub fov: f32,
+
+    /// Maximum peripheral viewing distance in `meters`.
+    pub peripheral_distance: f32,
+
+    /// Full angle of the peripheral vision cone in `radians`.
+    pub peripheral_fov: f32,
+
+    /// Multiplier on both viewing distances, raised by the [`AlertLevel`].
+    pub range_scale: f32,
+}
+
+impl Default for Vision {
+    fn default() -> Self {
+        Self {
+            distance: 10.0,
+            fov: FRAC_PI_2,
+            peripheral_distance: 0.0,
+            peripheral_fov: 0.0,
+            range_scale: 1.0,
+        }
+    }
+}
+
+impl Vision {
+    /// Checks if a point falls inside the vision cones of an entity.
+    ///
+    /// Entities face their local +Z axis, and the test is done on the XZ
+    /// plane.
+    pub fn cone_contains(
+        &self,
+        transform: &GlobalTransform,
+        point: Vec3,
+    ) -> bool {
+        let diff = (point - transform.translation()).xz();
+
+        if diff == Vec2::ZERO {
+            return true;
+        }
+
+        let facing = transform.back().xz().normalize_or_zero();
+        let sector_contains = |distance: f32, fov: f32| {
+            let distance = distance * self.range_scale;
+
+            diff.length_squared() <= distance * distance
+                && facing.angle_between(diff).abs() <= fov * 0.5
+        };
+
+        sector_contains(self.distance, self.fov)
+            || sector_contains(self.peripheral_distance, self.peripheral_fov)
+    }
+
+    /// Checks that the vision cones make sense.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.distance > 0.0 && self.distance.is_finite()) {
+            return Err(format!(
+                "distance must be positive, but is {}",
+                self.distance
+            ));
+        }
+
+        if !(self.fov > 0.0 && self.fov <= TAU) {
+            return Err(format!(
+                "fov must be within (0, 2π] radians, but is {}",
+                self.fov
+            ));
+        }
+
+        if !(0.0..=self.distance).contains(&self.peripheral_distance) {
+            return Err(format!(
+                "peripheral_distance must be within [0, distance], but is {}",
+                self.peripheral_distance
+            ));
+        }
+
+        if self.peripheral_distance > 0.0
+            && !(self.fov..=TAU).contains(&self.peripheral_fov)
+        {
+            return Err(format!(
+                "peripheral_fov must be within [fov, 2π] radians, but is {}",
+                self.peripheral_fov
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The player's location while they're seen by an entity with [`Vision`].
+#[derive(Clone, Component, Debug)]
+pub struct SeesPlayer(pub Vec3);
+
+/// State machine trigger for when an entity with [`Vision`] sees the player.
+pub fn saw_player(
+    In(entity): In<Entity>,
+    query: Query<Option<&SeesPlayer>>,
+) -> Option<Vec3> {
+    let sees_player = query.get(entity).unwrap();
+
+    sees_player.map(|sees_player| sees_player.0)
+}
+
+fn look_for_player(
+    mut commands: Commands,
+    line_of_sight: LineOfSight,
+    viewer_query: Query<(Entity, &GlobalTransform, &Vision, Has<StunnedFor>)>,
+    player_query: Query<
+        &GlobalTransform,
+        (With<Player>, Without<Invisibility>),
+    >,
+) {
+    let player_location = player_query
+        .get_single()
+        .ok()
+        .map(|player_transform| player_transform.translation());
+
+    for (entity, transform, vision, is_stunned) in &viewer_query {
+        let eye_offset = Vec3::Y * EYE_HEIGHT;
+        let seen_location = player_location.filter(|player_location| {
+            !is_stunned
+                && vision.cone_contains(transform, *player_location)
+                && line_of_sight.is_clear(
+                    transform.translation() + eye_offset,
+                    *player_location + eye_offset,
+                )
+        });
+
+        match seen_location {
+            Some(player_location) => {
+                commands.entity(entity).insert(SeesPlayer(player_location));
+            },
+            None => {
+                commands.entity(entity).remove::<SeesPlayer>();
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn vision() -> Vision {
+        Vision {
+            distance: 10.0,
+            fov: FRAC_PI_2,
+            peripheral_distance: 4.0,
+            peripheral_fov: PI,
+            range_scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_sensible_cones() {
+        assert!(Vision::default().validate().is_ok());
+        assert!(vision().validate().is_ok());
+        assert!(Vision {
+            fov: TAU,
+            peripheral_distance: 10.0,
+            peripheral_fov: TAU,
+            ..vision()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_distances() {
+        for distance in [0.0, -1.0, f32::INFINITY, f32::NAN] {
+            assert!(Vision {
+                distance,
+                ..vision()
+            }
+            .validate()
+            .is_err());
+        }
+
+        for peripheral_distance in [-1.0, 11.0] {
+            assert!(Vision {
+                peripheral_distance,
+                ..vision()
+            }
+            .validate()
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_angles() {
+        for fov in [0.0, -1.0, TAU + 0.1] {
+            assert!(Vision { fov, ..vision() }.validate().is_err());
+        }
+
+        for peripheral_fov in [FRAC_PI_2 - 0.1, TAU + 0.1] {
+            assert!(Vision {
+                peripheral_fov,
+                ..vision()
+            }
+            .validate()
+            .is_err());
+        }
+
+        // Without peripheral vision its angle doesn't matter.
+        assert!(Vision {
+            peripheral_distance: 0.0,
+            peripheral_fov: 0.0,
+            ..vision()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn cone_contains_points_in_the_main_cone() {
+        let transform = GlobalTransform::IDENTITY;
+
+        assert!(vision().cone_contains(&transform, Vec3::new(0.0, 0.0, 9.0)));
+        assert!(vision().cone_contains(&transform, Vec3::new(5.0, 0.0, 6.0)));
+        assert!(!vision().cone_contains(&transform, Vec3::new(0.0, 0.0, 11.0)));
+        assert!(Vision {
+            range_scale: 2.0,
+            ..vision()
+        }
+        .cone_contains(&transform, Vec3::new(0.0, 0.0, 11.0)));
+    }
+
+    #[test]
+    fn cone_contains_points_in_the_peripheral_cone() {
+        let transform = GlobalTransform::IDENTITY;
+
+        assert!(vision().cone_contains(&transform, Vec3::new(3.0, 0.0, 1.0)));
+        assert!(!vision().cone_contains(&transform, Vec3::new(5.0, 0.0, 1.0)));
+        assert!(!vision().cone_contains(&transform, Vec3::new(0.0, 0.0, -1.0)));
+        assert!(!Vision {
+            peripheral_distance: 0.0,
+            ..vision()
+        }
+        .cone_contains(&transform, Vec3::new(3.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn cone_contains_points_at_zero_distance() {
+        let transform = GlobalTransform::from_translation(Vec3::ONE);
+
+        assert!(vision().cone_contains(&transform, Vec3::ONE));
+        assert!(vision().cone_contains(&transform, Vec3::new(1.0, 5.0, 1.0)));
+    }
+}
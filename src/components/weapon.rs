@@ -1,5 +1,377 @@
-use bevy::ecs::prelude::*;
+use avian3d::prelude::*;
+use bevy::{math::Vec3Swizzles, prelude::*};
+use leafwing_input_manager::prelude::*;
+use serde::Deserialize;
+use std::{mem::discriminant, time::Duration};
+
+use crate::prelude::*;
+
+/// Height above the ground that the aiming preview is drawn.
+const AIM_PREVIEW_HEIGHT: f32 = 0.5;
+
+/// Height above the ground that shockwaves are tested for walls at.
+const BLAST_HEIGHT: f32 = 0.5;
+
+pub(super) struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (cool_down_weapons, stock_new_weapons)
+                    .chain()
+                    .in_set(StoppedWhenPausedSet),
+                (
+                    cycle_equipped_weapon,
+                    fire_equipped_weapon,
+                    draw_aim_preview,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            ),
+        )
+        .observe(blast_shockwave);
+    }
+}
 
 /// A weapon that can be equipped by the player.
-#[derive(Clone, Component, Debug, Default)]
-pub struct Weapon;
+#[derive(Clone, Component, Debug)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+
+    /// Shots left before the weapon is empty.
+    pub ammo: u32,
+
+    /// Time that has to pass between shots.
+    pub cooldown: Timer,
+
+    /// Sound wave emitted when the shot lands.
+    pub sound_wave: Handle<SoundWaveConfig>,
+}
+
+impl Weapon {
+    pub fn new(
+        kind: WeaponKind,
+        ammo: u32,
+        cooldown: f32,
+        sound_wave: Handle<SoundWaveConfig>,
+    ) -> Self {
+        let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+
+        // Ready to fire as soon as it's picked up.
+        cooldown.tick(cooldown.duration());
+
+        Self {
+            kind,
+            ammo,
+            cooldown,
+            sound_wave,
+        }
+    }
+
+    /// Whether the weapon has ammo and has finished cooling down.
+    pub fn is_ready(&self) -> bool {
+        self.ammo > 0 && self.cooldown.finished()
+    }
+}
+
+/// How a [`Weapon`] behaves when fired.
+#[derive(Clone, Debug, Deserialize)]
+pub enum WeaponKind {
+    /// Fires a projectile that ricochets off walls that deflect sounds and
+    /// makes a distracting sound where it lands.
+    SoundGun {
+        /// Projectile speed in `meters/second`.
+        speed: f32,
+
+        /// How far the projectile travels in `meters` before landing.
+        range: f32,
+
+        /// Most walls the projectile can bounce off before landing.
+        bounces: u32,
+    },
+
+    /// Blasts a cone in front of the player that stuns guards, shatters glass,
+    /// and pushes dynamic bodies, while emitting a huge sound wave.
+    ShockwaveCannon {
+        /// How far the shockwave reaches in `meters`.
+        radius: f32,
+
+        /// Full angle of the cone in `radians`.
+        spread: f32,
+
+        /// How long guards stay stunned in `seconds`.
+        stun_duration: f32,
+
+        /// Impulse applied to dynamic bodies in `newton-seconds`.
+        impulse: f32,
+    },
+}
+
+/// Direction the player is aiming in, if they're aiming at all.
+fn aim_direction(action_state: &ActionState<PlayerAction>) -> Option<Dir3> {
+    let aim_axis = action_state.clamped_axis_pair(&PlayerAction::Aim).xy();
+
+    Dir3::new(Vec3::new(aim_axis.x, 0.0, -aim_axis.y)).ok()
+}
+
+fn cool_down_weapons(time: Res<Time>, mut query: Query<&mut Weapon>) {
+    for mut weapon in &mut query {
+        weapon.cooldown.tick(time.delta());
+    }
+}
+
+fn stock_new_weapons(
+    mut commands: Commands,
+    new_weapon_query: Query<(Entity, &Parent), Added<Weapon>>,
+    owner_query: Query<(&Children, Has<EquippedFirearm>)>,
+    mut weapon_query: Query<&mut Weapon>,
+) {
+    for (entity, parent) in &new_weapon_query {
+        let Ok((children, has_equipped_firearm)) =
+            owner_query.get(parent.get())
+        else {
+            continue;
+        };
+        let Ok(new_weapon) = weapon_query.get(entity).cloned() else {
+            continue;
+        };
+
+        // Picking up a weapon the owner already has just tops up its ammo.
+        let existing_weapon = children.iter().copied().find(|child| {
+            *child != entity
+                && weapon_query.get(*child).is_ok_and(|weapon| {
+                    discriminant(&weapon.kind) == discriminant(&new_weapon.kind)
+                })
+        });
+
+        if let Some(existing_weapon) = existing_weapon {
+            let mut weapon = weapon_query.get_mut(existing_weapon).unwrap();
+
+            weapon.ammo += new_weapon.ammo;
+            commands.entity(entity).despawn_recursive();
+        } else if !has_equipped_firearm {
+            commands
+                .entity(parent.get())
+                .insert(EquippedFirearm(entity));
+        }
+    }
+}
+
+fn cycle_equipped_weapon(
+    mut query: Query<
+        (&ActionState<PlayerAction>, &Children, &mut EquippedFirearm),
+        With<Player>,
+    >,
+    weapon_query: Query<(), With<Weapon>>,
+) {
+    for (action_state, children, mut equipped_firearm) in &mut query {
+        if !action_state.just_pressed(&PlayerAction::CycleWeapon) {
+            continue;
+        }
+
+        let weapons: Vec<Entity> = children
+            .iter()
+            .copied()
+            .filter(|child| weapon_query.contains(*child))
+            .collect();
+        let Some(index) = weapons
+            .iter()
+            .position(|weapon| *weapon == equipped_firearm.0)
+        else {
+            continue;
+        };
+
+        equipped_firearm.0 = weapons[(index + 1) % weapons.len()];
+    }
+}
+
+fn fire_equipped_weapon(
+    mut commands: Commands,
+    sound_barriers: SoundBarriers,
+    player_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ActionState<PlayerAction>,
+            &EquippedFirearm,
+        ),
+        With<Player>,
+    >,
+    mut weapon_query: Query<&mut Weapon>,
+) {
+    for (entity, transform, action_state, equipped_firearm) in &player_query {
+        if !action_state.just_pressed(&PlayerAction::Fire) {
+            continue;
+        }
+
+        let Ok(mut weapon) = weapon_query.get_mut(equipped_firearm.0) else {
+            continue;
+        };
+
+        if !weapon.is_ready() {
+            continue;
+        }
+
+        weapon.ammo -= 1;
+        weapon.cooldown.reset();
+
+        let aim_direction =
+            aim_direction(action_state).unwrap_or(transform.back());
+        let origin = transform.translation();
+
+        match weapon.kind {
+            WeaponKind::SoundGun {
+                speed,
+                range,
+                bounces,
+            } => {
+                commands.spawn((
+                    ForStates::new(GameState::IN_LEVEL),
+                    Projectile {
+                        shooter: entity,
+                        path: sound_barriers.trace_bounces(
+                            origin,
+                            aim_direction,
+                            range,
+                            bounces,
+                        ),
+                        speed,
+                        traveled_distance: 0.0,
+                        sound_wave: weapon.sound_wave.clone(),
+                    },
+                ));
+            },
+            WeaponKind::ShockwaveCannon {
+                radius,
+                spread,
+                stun_duration,
+                impulse,
+            } => {
+                commands.trigger(Shockwave::new(
+                    entity,
+                    origin,
+                    aim_direction,
+                    radius,
+                    spread,
+                    Duration::from_secs_f32(stun_duration),
+                    impulse,
+                ));
+                commands.trigger(
+                    SpawnSoundWave::new(weapon.sound_wave.clone(), origin)
+                        .with_emitter(entity),
+                );
+            },
+        }
+    }
+}
+
+fn blast_shockwave(
+    trigger: Trigger<Shockwave>,
+    mut commands: Commands,
+    sound_barriers: SoundBarriers,
+    query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Option<&Collider>,
+            Option<&RigidBody>,
+            Has<Stunnable>,
+            Has<Glass>,
+        ),
+        Or<(With<Stunnable>, With<Glass>, With<RigidBody>)>,
+    >,
+) {
+    let shockwave = trigger.event();
+    let blast_offset = Vec3::Y * BLAST_HEIGHT;
+
+    for (entity, transform, collider, rigid_body, is_stunnable, is_glass) in
+        &query
+    {
+        if entity == shockwave.shooter {
+            continue;
+        }
+
+        // Aim for the nearest part of large targets, like panes of glass,
+        // rather than their center.
+        let (_, rotation, translation) =
+            transform.to_scale_rotation_translation();
+        let target = collider.map_or(translation, |collider| {
+            collider
+                .project_point(translation, rotation, shockwave.origin, true)
+                .0
+        });
+        let diff = (target - shockwave.origin).with_y(0.0);
+
+        if diff.length() > shockwave.radius
+            || shockwave.direction.xz().angle_between(diff.xz()).abs()
+                > shockwave.spread * 0.5
+        {
+            continue;
+        }
+
+        // Walls shield whatever is behind them from the blast.
+        if !sound_barriers.is_clear(
+            shockwave.origin.with_y(0.0) + blast_offset,
+            target.with_y(0.0) + blast_offset,
+            entity,
+        ) {
+            continue;
+        }
+
+        if is_stunnable {
+            commands
+                .trigger_targets(Stun::new(shockwave.stun_duration), entity);
+        }
+
+        if is_glass {
+            commands.trigger_targets(Shatter, entity);
+        }
+
+        if rigid_body.is_some_and(RigidBody::is_dynamic) {
+            commands.entity(entity).insert(ExternalImpulse::new(
+                diff.normalize_or(*shockwave.direction) * shockwave.impulse,
+            ));
+        }
+    }
+}
+
+fn draw_aim_preview(
+    mut gizmos: Gizmos,
+    sound_barriers: SoundBarriers,
+    player_query: Query<
+        (
+            &GlobalTransform,
+            &ActionState<PlayerAction>,
+            &EquippedFirearm,
+        ),
+        With<Player>,
+    >,
+    weapon_query: Query<&Weapon>,
+) {
+    for (transform, action_state, equipped_firearm) in &player_query {
+        let Ok(Weapon {
+            kind: WeaponKind::SoundGun { range, bounces, .. },
+            ..
+        }) = weapon_query.get(equipped_firearm.0)
+        else {
+            continue;
+        };
+        let Some(aim_direction) = aim_direction(action_state) else {
+            continue;
+        };
+        let path = sound_barriers.trace_bounces(
+            transform.translation(),
+            aim_direction,
+            *range,
+            *bounces,
+        );
+
+        gizmos.linestrip(
+            path.into_iter()
+                .map(|point| point.with_y(AIM_PREVIEW_HEIGHT)),
+            Color::WHITE,
+        );
+    }
+}
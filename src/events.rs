@@ -1,13 +1,110 @@
 use bevy::prelude::*;
+use derive_new::new;
+use std::time::Duration;
+
+use crate::prelude::*;
 
 pub struct EventsPlugin;
 
 impl Plugin for EventsPlugin {
     fn build(&self, app: &mut App) {
-        //app.add_event::<HeardNoiseEvent>();
+        app.add_event::<HeardNoiseEvent>()
+            .add_event::<AlarmEvent>()
+            .add_event::<PickupCollectedEvent>()
+            .add_event::<PlayerCaughtEvent>();
     }
 }
 
+/// A sound wave reached an entity that has
+/// [`Hearing`](crate::components::Hearing).
+#[derive(Clone, Debug, Event)]
+pub struct HeardNoiseEvent {
+    /// Entity that heard the sound wave.
+    pub listener: Entity,
+
+    /// Entity responsible for making the sound, if any.
+    pub emitter: Option<Entity>,
+
+    /// Point the sound wave started expanding from.
+    pub origin: Vec3,
+
+    /// Point the sound seemed to come from, which is where the wave front that
+    /// reached the listener last bounced, or the origin if it never did.
+    pub source: Vec3,
+}
+
+/// An alarm went off, alerting everything that can hear it.
+#[derive(Clone, Debug, Event)]
+pub struct AlarmEvent {
+    /// Point the alarm went off from.
+    pub origin: Vec3,
+
+    /// How far the alarm can be heard in `meters`.
+    pub radius: f32,
+
+    /// Where the player was when the alarm was raised.
+    pub player_location: Vec3,
+}
+
+/// The player collected a [`Pickup`].
+#[derive(Clone, Debug, Event)]
+pub struct PickupCollectedEvent {
+    pub pickup: Entity,
+    pub collector: Entity,
+    pub kind: PickupKind,
+}
+
+/// A [`Guard`] caught the player, ending the game.
+#[derive(Clone, Debug, Event)]
+pub struct PlayerCaughtEvent {
+    pub guard: Entity,
+    pub player: Entity,
+}
+
+/// Sets off every [`Alarm`](crate::components::Alarm) sharing a channel with
+/// the camera that spotted the player.
+#[derive(Clone, Copy, Debug, Event, new)]
+pub struct RaiseAlarm {
+    /// Where the player was when they were spotted.
+    pub player_location: Vec3,
+
+    /// Alarms to set off, if the camera is linked to any.
+    pub channel: Option<AlarmChannel>,
+}
+
+/// Shatters the targeted entity if it's [`Glass`](crate::components::Glass).
+#[derive(Clone, Debug, Event)]
+pub struct Shatter;
+
+/// Blasts everything in a cone, stunning guards, shattering glass, and pushing
+/// dynamic bodies away.
+#[derive(Clone, Debug, Event, new)]
+pub struct Shockwave {
+    /// Entity that fired the shockwave.
+    pub shooter: Entity,
+
+    pub origin: Vec3,
+    pub direction: Dir3,
+
+    /// How far the shockwave reaches in `meters`.
+    pub radius: f32,
+
+    /// Full angle of the cone in `radians`.
+    pub spread: f32,
+
+    pub stun_duration: Duration,
+
+    /// Impulse applied to dynamic bodies in `newton-seconds`.
+    pub impulse: f32,
+}
+
+/// Stuns the targeted entity if it's
+/// [`Stunnable`](crate::components::Stunnable).
+#[derive(Clone, Debug, Event, new)]
+pub struct Stun {
+    pub duration: Duration,
+}
+
 // UiMessageEvent
 // position: Top | Bottom
 // text: String
@@ -62,6 +62,21 @@ pub enum GameState {
     Paused,
     Gameplay,
     GameOver,
+    LevelComplete,
+    LevelTransition,
+    CampaignComplete,
+    InvalidCampaign,
+}
+
+impl GameState {
+    /// States that a level's entities exist in, so they get torn down when the
+    /// level is left.
+    pub const IN_LEVEL: [Self; 4] = [
+        Self::Paused,
+        Self::Gameplay,
+        Self::GameOver,
+        Self::LevelComplete,
+    ];
 }
 
 /// Systems that run outside the loading state.
@@ -1,5 +1,6 @@
 mod actions;
 mod assets;
+mod campaign;
 mod components;
 mod events;
 mod game_state;
@@ -11,6 +12,7 @@ mod util;
 mod prelude {
     pub use crate::actions::*;
     pub use crate::assets::*;
+    pub use crate::campaign::*;
     pub use crate::components::*;
     pub use crate::events::*;
     pub use crate::game_state::*;
@@ -30,6 +32,7 @@ impl Plugin for BevyStealthGameExamplePlugin {
         app.add_plugins((
             ActionsPlugin,
             AssetsPlugin,
+            CampaignPlugin,
             ComponentsPlugin,
             GameStatePlugin,
             EventsPlugin,
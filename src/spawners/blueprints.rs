@@ -1,4 +1,7 @@
-use bevy::{ecs::system::SystemState, prelude::*, utils::HashMap};
+use avian3d::prelude::{Collider, CollidingEntities, RigidBody, Sensor};
+use bevy::{
+    ecs::system::SystemState, gltf::GltfMesh, prelude::*, utils::HashMap,
+};
 use bevy_common_assets::ron::RonAssetPlugin;
 use derive_new::new;
 use serde::Deserialize;
@@ -20,6 +23,38 @@ pub struct SpawnEntityFromBlueprint {
     #[new(into)]
     blueprint_name: String,
     matrix: Mat4,
+
+    #[new(default)]
+    overrides: Vec<BlueprintProp>,
+
+    #[new(default)]
+    parent: Option<Entity>,
+
+    #[new(default)]
+    mesh: Option<Handle<GltfMesh>>,
+}
+
+impl SpawnEntityFromBlueprint {
+    /// Adds per-instance properties that are applied after the blueprint's.
+    pub fn with_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = BlueprintProp>,
+    ) -> Self {
+        self.overrides.extend(overrides);
+        self
+    }
+
+    /// Spawns the entity as a child of another entity.
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Renders a glTF mesh in the entity's local space.
+    pub fn with_mesh(mut self, mesh: Handle<GltfMesh>) -> Self {
+        self.mesh = Some(mesh);
+        self
+    }
 }
 
 /// Asset for spawning and configuring entities.
@@ -33,12 +68,41 @@ pub struct Blueprint(pub Vec<BlueprintProp>);
 pub enum BlueprintProp {
     Player,
     Guard,
-    SecurityCamera,
-    Pickup,
-    Weapon,
-    FloorSwitch,
+    SecurityCamera {
+        sweep_angle: f32,
+        pause: f32,
+    },
+    Alarm {
+        radius: f32,
+    },
+    AlarmChannel(u32),
+    Pickup {
+        kind: PickupKind,
+        #[serde(default)]
+        respawn_delay: Option<f32>,
+    },
+    Weapon {
+        kind: WeaponKind,
+        ammo: u32,
+        cooldown: f32,
+        sound_wave: String,
+    },
+    FloorSwitch {
+        radius: f32,
+        reset_delay: f32,
+    },
+    SwitchChannel(u32),
+    AlertResponse {
+        tier: AlertTier,
+        response: DoorResponse,
+    },
     Door,
     Glass,
+    LevelExit,
+    CharacterController {
+        acceleration: f32,
+        deceleration: f32,
+    },
     Speed {
         linear_speed: f32,
         angular_speed: f32,
@@ -48,14 +112,32 @@ pub enum BlueprintProp {
     },
     Footsteps {
         sound_wave: String,
+        stride: f32,
+    },
+    Patrol {
+        mode: PatrolMode,
+        waypoints: Vec<Waypoint>,
+        #[serde(default)]
+        alerted_waypoints: Vec<Waypoint>,
     },
     DropShadow,
-    Vision,
-    Hearing,
+    Vision {
+        distance: f32,
+        fov: f32,
+        #[serde(default)]
+        peripheral_distance: f32,
+        #[serde(default)]
+        peripheral_fov: f32,
+    },
+    Hearing {
+        radius: f32,
+    },
     Stunnable,
     Barrier,
     BlocksVision,
     DeflectsSounds,
+    NavMeshObstacle,
+    BoxCollider,
     Scene(String),
     AnimationClips(HashMap<String, String>),
 }
@@ -123,44 +205,105 @@ fn spawn_entity_from_blueprint(
     mut commands: Commands,
     mut graphs: ResMut<Assets<AnimationGraph>>,
     blueprints: Res<Assets<Blueprint>>,
+    gltf_meshes: Res<Assets<GltfMesh>>,
     game_assets: Res<GameAssets>,
     preloaded_blueprint_assets: Res<PreloadedBlueprintAssets>,
 ) {
     let SpawnEntityFromBlueprint {
         blueprint_name,
         matrix,
+        overrides,
+        parent,
+        mesh,
     } = trigger.event();
     let handle = game_assets.blueprints.get(blueprint_name.as_str()).unwrap();
     let blueprint = blueprints.get(handle).unwrap();
-    let mut entity_commands = commands.spawn(ForStates::new([
-        GameState::Paused,
-        GameState::Gameplay,
-        GameState::GameOver,
-    ]));
+    let transform = Transform::from_matrix(*matrix);
+    let mut entity_commands = commands.spawn((
+        ForStates::new(GameState::IN_LEVEL),
+        SpatialBundle::from_transform(transform),
+    ));
+
+    if let Some(parent) = parent {
+        entity_commands.set_parent(*parent);
+    }
 
-    for property in &blueprint.0 {
+    if let Some(gltf_mesh) =
+        mesh.as_ref().and_then(|mesh| gltf_meshes.get(mesh))
+    {
+        entity_commands.with_children(|builder| {
+            for primitive in &gltf_mesh.primitives {
+                builder.spawn(PbrBundle {
+                    mesh: primitive.mesh.clone(),
+                    material: primitive.material.clone().unwrap_or_default(),
+                    ..default()
+                });
+            }
+        });
+    }
+
+    for property in blueprint.0.iter().chain(overrides) {
         match property {
             BlueprintProp::Player => {
                 entity_commands.insert(PlayerBundle::default());
             },
             BlueprintProp::Guard => {
-                entity_commands.insert(GuardBundle::with_starting_location(
+                entity_commands
+                    .insert(GuardBundle::with_starting_location(transform));
+            },
+            BlueprintProp::SecurityCamera { sweep_angle, pause } => {
+                entity_commands.insert(SecurityCameraBundle::new(
                     Transform::from_matrix(*matrix),
+                    *sweep_angle,
+                    *pause,
                 ));
             },
-            BlueprintProp::SecurityCamera => {
-                entity_commands.insert(SecurityCameraBundle::default());
+            BlueprintProp::Alarm { radius } => {
+                entity_commands.insert(Alarm { radius: *radius });
             },
-            BlueprintProp::Pickup => {
-                entity_commands.insert(PickupBundle::default());
+            BlueprintProp::AlarmChannel(channel) => {
+                entity_commands.insert(AlarmChannel(*channel));
             },
-            BlueprintProp::Weapon => {
-                entity_commands.insert(Weapon::default());
+            BlueprintProp::Pickup {
+                kind,
+                respawn_delay,
+            } => {
+                entity_commands
+                    .insert((PickupBundle::new(*kind, *respawn_delay), Sensor));
+            },
+            BlueprintProp::Weapon {
+                kind,
+                ammo,
+                cooldown,
+                sound_wave,
+            } => {
+                let sound_wave_handle =
+                    game_assets.sound_waves.get(sound_wave.as_str()).unwrap();
+
+                entity_commands.insert(Weapon::new(
+                    kind.clone(),
+                    *ammo,
+                    *cooldown,
+                    sound_wave_handle.clone(),
+                ));
             },
             //Trigger {} // TODO: Probably want to have a sub-enum with
             // pre-allowed events?
-            BlueprintProp::FloorSwitch => {
-                entity_commands.insert(FloorSwitchBundle::default());
+            BlueprintProp::FloorSwitch {
+                radius,
+                reset_delay,
+            } => {
+                entity_commands
+                    .insert(FloorSwitchBundle::new(*radius, *reset_delay));
+            },
+            BlueprintProp::SwitchChannel(channel) => {
+                entity_commands.insert(SwitchChannel(*channel));
+            },
+            BlueprintProp::AlertResponse { tier, response } => {
+                entity_commands.insert(AlertResponse {
+                    tier: *tier,
+                    response: *response,
+                });
             },
             BlueprintProp::Door => {
                 entity_commands.insert(DoorBundle::default());
@@ -168,6 +311,22 @@ fn spawn_entity_from_blueprint(
             BlueprintProp::Glass => {
                 entity_commands.insert(GlassBundle::default());
             },
+            BlueprintProp::LevelExit => {
+                entity_commands.insert((
+                    LevelExit,
+                    Sensor,
+                    CollidingEntities::default(),
+                ));
+            },
+            BlueprintProp::CharacterController {
+                acceleration,
+                deceleration,
+            } => {
+                entity_commands.insert(CharacterControllerBundle::new(
+                    *acceleration,
+                    *deceleration,
+                ));
+            },
             BlueprintProp::Speed {
                 linear_speed,
                 angular_speed,
@@ -179,28 +338,93 @@ fn spawn_entity_from_blueprint(
                 });
             },
             BlueprintProp::Physics { radius } => {
-                // TODO: Need a component for this one.
+                let physics = Physics { radius: *radius };
+
+                entity_commands.insert((
+                    RigidBody::Kinematic,
+                    physics.collider(transform.scale),
+                    CollidingEntities::default(),
+                    physics,
+                ));
             },
-            BlueprintProp::Footsteps { sound_wave } => {
+            BlueprintProp::Footsteps { sound_wave, stride } => {
                 let sound_wave_handle =
                     game_assets.sound_waves.get(sound_wave.as_str()).unwrap();
 
-                entity_commands.insert(FootstepsBundle {
-                    footsteps: Footsteps {
-                        sound_wave: sound_wave_handle.clone(),
+                let footsteps = Footsteps {
+                    sound_wave: sound_wave_handle.clone(),
+                    stride: *stride,
+                };
+
+                match footsteps.validate() {
+                    Ok(()) => {
+                        entity_commands.insert(FootstepsBundle {
+                            footsteps,
+                            walked_distance: WalkedDistance::default(),
+                        });
                     },
+                    Err(message) => {
+                        error!(
+                            "Skipping invalid Footsteps in `{blueprint_name}`: \
+                             {message}"
+                        );
+                    },
+                }
+            },
+            BlueprintProp::Patrol {
+                mode,
+                waypoints,
+                alerted_waypoints,
+            } => {
+                entity_commands.insert(Patrol {
+                    mode: *mode,
+                    waypoints: waypoints.clone(),
+                    alerted_waypoints: alerted_waypoints.clone(),
                 });
             },
             BlueprintProp::DropShadow => {
                 entity_commands.insert(DropShadow::default());
             },
-            BlueprintProp::Vision => {
-                // TODO: Implement setting the fields.
-                entity_commands.insert(Vision::default());
+            BlueprintProp::Vision {
+                distance,
+                fov,
+                peripheral_distance,
+                peripheral_fov,
+            } => {
+                let vision = Vision {
+                    distance: *distance,
+                    fov: *fov,
+                    peripheral_distance: *peripheral_distance,
+                    peripheral_fov: *peripheral_fov,
+                    range_scale: 1.0,
+                };
+
+                match vision.validate() {
+                    Ok(()) => {
+                        entity_commands.insert(vision);
+                    },
+                    Err(message) => {
+                        error!(
+                            "Skipping invalid Vision in `{blueprint_name}`: \
+                             {message}"
+                        );
+                    },
+                }
             },
-            BlueprintProp::Hearing => {
-                // TODO: Implement setting the fields.
-                entity_commands.insert(Hearing::default());
+            BlueprintProp::Hearing { radius } => {
+                let hearing = Hearing { radius: *radius };
+
+                match hearing.validate() {
+                    Ok(()) => {
+                        entity_commands.insert(hearing);
+                    },
+                    Err(message) => {
+                        error!(
+                            "Skipping invalid Hearing in `{blueprint_name}`: \
+                             {message}"
+                        );
+                    },
+                }
             },
             BlueprintProp::Stunnable => {
                 entity_commands.insert(Stunnable::default());
@@ -214,6 +438,16 @@ fn spawn_entity_from_blueprint(
             BlueprintProp::DeflectsSounds => {
                 entity_commands.insert(DeflectsSounds::default());
             },
+            BlueprintProp::NavMeshObstacle => {
+                entity_commands.insert(NavMeshObstacle::default());
+            },
+            BlueprintProp::BoxCollider => {
+                // Sized by the scale of the spawning matrix.
+                entity_commands.insert((
+                    RigidBody::Static,
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                ));
+            },
             BlueprintProp::AnimationClips(clips) => {
                 let mut loaded_clips = HashMap::default();
                 let mut graph = AnimationGraph::new();
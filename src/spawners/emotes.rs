@@ -10,9 +10,23 @@ impl Plugin for EmotesPlugin {
     }
 }
 
-/// Configs for spawnable entities.
+/// Configs for emotes that entities can show.
 #[derive(Asset, Debug, Deserialize, Resource, TypePath)]
 pub struct EmoteConfig {
-    pub image: String,
+    pub symbol: EmoteSymbol,
+
+    /// Hex color code used when drawing the emote.
+    pub color: String,
+
+    /// How long the emote lasts in `seconds`.
+    pub lifetime: f32,
     // sound??
 }
+
+/// Shapes that an emote can be drawn as.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum EmoteSymbol {
+    Exclamation,
+    Question,
+    Ellipsis,
+}
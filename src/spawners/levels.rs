@@ -0,0 +1,521 @@
+use avian3d::prelude::{Collider, RigidBody};
+use bevy::{
+    color::palettes,
+    ecs::system::SystemState,
+    gltf::{Gltf, GltfMesh, GltfNode},
+    prelude::*,
+    reflect::TypePath,
+    utils::{HashMap, HashSet},
+};
+use bevy_common_assets::ron::RonAssetPlugin;
+use derive_new::new;
+use polyanya::Triangulation;
+use serde::Deserialize;
+use std::f32::consts::FRAC_PI_2;
+use vleue_navigator::{prelude::*, NavMesh};
+
+use crate::prelude::*;
+
+/// Small lift that keeps the ground from z-fighting with the navmesh.
+const GROUND_OFFSET: f32 = 0.01;
+
+/// How much obstacle outlines are simplified before being cut from navmeshes.
+const NAVMESH_SIMPLIFY: f32 = 0.001;
+
+pub(super) struct LevelsPlugin;
+
+impl Plugin for LevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Level>::new(&["level.ron"]))
+            .observe(spawn_level);
+    }
+}
+
+/// Spawn a level's geometry, lighting, and entities.
+#[derive(Event, new)]
+pub struct SpawnLevel {
+    #[new(into)]
+    level_name: String,
+}
+
+/// Asset describing the layout of a level.
+#[derive(Asset, Debug, Deserialize, Resource, TypePath)]
+pub struct Level {
+    /// Static geometry the level is built around.
+    pub geometry: LevelGeometry,
+
+    /// Lights placed in the level.
+    #[serde(default)]
+    pub lights: Vec<LevelLight>,
+
+    /// Entities placed in the level.
+    #[serde(default)]
+    pub entities: Vec<LevelEntity>,
+}
+
+/// Named meshes inside a glTF file that make up a level's static geometry.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LevelGeometry {
+    /// Path to the glTF file.
+    pub gltf: String,
+
+    /// Mesh for walls and other obstacles that block movement, sight, and
+    /// sound.
+    pub obstacles: String,
+
+    /// Mesh for the floor.
+    pub ground: String,
+
+    /// Where the walkable area of the level comes from.
+    pub navmesh: NavMeshSource,
+}
+
+/// Ways of providing a level's navmesh.
+#[derive(Clone, Debug, Deserialize)]
+pub enum NavMeshSource {
+    /// A pre-authored mesh in the level's glTF file.
+    Mesh(String),
+
+    /// Built at runtime across the ground by cutting out every collider with
+    /// a [`NavMeshObstacle`], and rebuilt whenever one is added or removed,
+    /// such as when doors open or glass shatters.
+    Generated {
+        /// How far in `meters` walkable space is kept from obstacles.
+        agent_radius: f32,
+    },
+}
+
+/// A light placed in a level.
+#[derive(Clone, Debug, Deserialize)]
+pub enum LevelLight {
+    Directional {
+        illuminance: f32,
+        direction: Vec3,
+        #[serde(default)]
+        shadows: bool,
+    },
+    Point {
+        /// Hex color code of the light.
+        color: String,
+        translation: Vec3,
+        intensity: f32,
+
+        /// How far the light reaches in `meters`.
+        range: f32,
+        #[serde(default)]
+        shadows: bool,
+    },
+}
+
+/// An entity placed in a level from a blueprint.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LevelEntity {
+    pub blueprint: String,
+
+    #[serde(default)]
+    pub translation: Vec3,
+
+    /// Rotation about the Y axis in `radians`.
+    #[serde(default)]
+    pub yaw: f32,
+
+    #[serde(default = "default_scale")]
+    pub scale: Vec3,
+
+    /// Properties applied on top of the blueprint's for this instance.
+    #[serde(default)]
+    pub overrides: Vec<BlueprintProp>,
+}
+
+impl LevelEntity {
+    /// Matrix the entity is spawned with.
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(
+            self.scale,
+            Quat::from_rotation_y(self.yaw),
+            self.translation,
+        )
+    }
+}
+
+fn default_scale() -> Vec3 {
+    Vec3::ONE
+}
+
+/// Assets that need to be loaded in advance of spawning levels.
+#[derive(Debug, Resource)]
+pub struct PreloadedLevelAssets {
+    pub geometry: HashMap<String, Handle<Gltf>>,
+}
+
+impl PreloadedLevelAssets {
+    /// Checks if every level's geometry has finished loading.
+    pub fn is_loaded(&self, asset_server: &AssetServer) -> bool {
+        self.geometry
+            .values()
+            .all(|gltf| asset_server.is_loaded_with_dependencies(gltf.id()))
+    }
+}
+
+impl FromWorld for PreloadedLevelAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut system_state: SystemState<(
+            Res<AssetServer>,
+            Res<GameAssets>,
+            Res<Assets<Level>>,
+        )> = SystemState::new(world);
+        let (asset_server, game_assets, level_assets) =
+            system_state.get_mut(world);
+        let mut geometry: HashMap<String, Handle<Gltf>> = HashMap::default();
+
+        for (_, level_handle) in &game_assets.levels {
+            let Some(level) = level_assets.get(level_handle) else {
+                continue;
+            };
+            let path = &level.geometry.gltf;
+
+            if geometry.get(path).is_none() {
+                geometry.insert(path.to_string(), asset_server.load(path));
+            }
+        }
+
+        Self { geometry }
+    }
+}
+
+fn spawn_level(
+    trigger: Trigger<SpawnLevel>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    levels: Res<Assets<Level>>,
+    gltfs: Res<Assets<Gltf>>,
+    gltf_meshes: Res<Assets<GltfMesh>>,
+    gltf_nodes: Res<Assets<GltfNode>>,
+    meshes: Res<Assets<Mesh>>,
+    game_assets: Res<GameAssets>,
+    preloaded_level_assets: Res<PreloadedLevelAssets>,
+) {
+    let SpawnLevel { level_name } = trigger.event();
+    let handle = game_assets.levels.get(level_name.as_str()).unwrap();
+    let level = levels.get(handle).unwrap();
+    let geometry = &level.geometry;
+    let gltf = gltfs
+        .get(&preloaded_level_assets.geometry[&geometry.gltf])
+        .unwrap();
+    let named_mesh = |name: &str| {
+        let Some(gltf_mesh) = gltf
+            .named_meshes
+            .get(name)
+            .and_then(|handle| gltf_meshes.get(handle))
+        else {
+            error!("Missing mesh `{name}` in `{}`", geometry.gltf);
+            return None;
+        };
+
+        Some(gltf_mesh.primitives[0].mesh.clone())
+    };
+    let for_states = || ForStates::new(GameState::IN_LEVEL);
+
+    // Geometry
+    let obstacles = named_mesh(&geometry.obstacles).and_then(|mesh| {
+        let Some(collider) =
+            meshes.get(&mesh).and_then(Collider::trimesh_from_mesh)
+        else {
+            error!(
+                "Can't build a collider from mesh `{}` in `{}`",
+                geometry.obstacles, geometry.gltf
+            );
+            return None;
+        };
+
+        Some((mesh, collider))
+    });
+
+    if let Some((mesh, collider)) = obstacles {
+        let mut entity_commands = commands.spawn((
+            for_states(),
+            PbrBundle {
+                mesh,
+                material: materials.add(StandardMaterial {
+                    base_color: palettes::css::GRAY.into(),
+                    perceptual_roughness: 1.0,
+                    ..default()
+                }),
+                ..default()
+            },
+            RigidBody::Static,
+            collider,
+            Barrier,
+            BlocksVision,
+            DeflectsSounds,
+        ));
+
+        if let NavMeshSource::Generated { .. } = geometry.navmesh {
+            entity_commands.insert(NavMeshObstacle);
+        }
+    }
+
+    if let Some(mesh) = named_mesh(&geometry.ground) {
+        commands.spawn((
+            for_states(),
+            PbrBundle {
+                mesh,
+                material: materials.add(StandardMaterial {
+                    base_color: palettes::css::ALICE_BLUE.into(),
+                    perceptual_roughness: 1.0,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(0.0, GROUND_OFFSET, 0.0),
+                ..default()
+            },
+        ));
+    }
+
+    // Navmesh
+    let navmesh_settings = match &geometry.navmesh {
+        NavMeshSource::Mesh(name) => named_mesh(name)
+            .and_then(|handle| meshes.get(&handle))
+            .map(|mesh| {
+                let navmesh = NavMesh::from_bevy_mesh(mesh);
+
+                NavMeshSettings {
+                    fixed: Triangulation::from_mesh(navmesh.get().as_ref(), 0),
+                    ..default()
+                }
+            }),
+        NavMeshSource::Generated { agent_radius } => {
+            named_mesh(&geometry.ground)
+                .and_then(|handle| meshes.get(&handle))
+                .and_then(Mesh::compute_aabb)
+                .map(|aabb| {
+                    let (min, max) = (aabb.min(), aabb.max());
+
+                    NavMeshSettings {
+                        fixed: Triangulation::from_outer_edges(&[
+                            Vec2::new(min.x, min.z),
+                            Vec2::new(max.x, min.z),
+                            Vec2::new(max.x, max.z),
+                            Vec2::new(min.x, max.z),
+                        ]),
+                        agent_radius: *agent_radius,
+                        simplify: NAVMESH_SIMPLIFY,
+                        ..default()
+                    }
+                })
+        },
+    };
+
+    if let Some(settings) = navmesh_settings {
+        commands.spawn((
+            for_states(),
+            NavMeshBundle {
+                settings: NavMeshSettings {
+                    build_timeout: Some(5.0),
+                    upward_shift: 0.5,
+                    ..settings
+                },
+                transform: Transform::from_rotation(Quat::from_rotation_x(
+                    FRAC_PI_2,
+                )),
+                update_mode: NavMeshUpdateMode::Direct,
+                ..NavMeshBundle::with_default_id()
+            },
+        ));
+    }
+
+    // Lighting
+    for light in &level.lights {
+        match light {
+            LevelLight::Directional {
+                illuminance,
+                direction,
+                shadows,
+            } => {
+                commands.spawn((
+                    for_states(),
+                    DirectionalLightBundle {
+                        directional_light: DirectionalLight {
+                            illuminance: *illuminance,
+                            shadows_enabled: *shadows,
+                            ..default()
+                        },
+                        transform: Transform::default()
+                            .looking_to(*direction, Vec3::Y),
+                        ..default()
+                    },
+                ));
+            },
+            LevelLight::Point {
+                color,
+                translation,
+                intensity,
+                range,
+                shadows,
+            } => {
+                let Ok(color) = Srgba::hex(color) else {
+                    error!("Invalid light color `{color}` in `{level_name}`");
+                    continue;
+                };
+
+                commands.spawn((
+                    for_states(),
+                    PointLightBundle {
+                        point_light: PointLight {
+                            color: color.into(),
+                            intensity: *intensity,
+                            range: *range,
+                            shadows_enabled: *shadows,
+                            ..default()
+                        },
+                        transform: Transform::from_translation(*translation),
+                        ..default()
+                    },
+                ));
+            },
+        }
+    }
+
+    // Entities
+    for entity in &level.entities {
+        commands.trigger(
+            SpawnEntityFromBlueprint::new(
+                entity.blueprint.as_str(),
+                entity.matrix(),
+            )
+            .with_overrides(entity.overrides.iter().cloned()),
+        );
+    }
+
+    // Only walk root nodes, since children are nested inside their parents.
+    let child_indices: HashSet<usize> = gltf
+        .nodes
+        .iter()
+        .filter_map(|handle| gltf_nodes.get(handle))
+        .flat_map(|node| node.children.iter().map(|child| child.index))
+        .collect();
+
+    for node in gltf
+        .nodes
+        .iter()
+        .filter_map(|handle| gltf_nodes.get(handle))
+    {
+        if !child_indices.contains(&node.index) {
+            spawn_entities_from_nodes(
+                &mut commands,
+                &game_assets,
+                node,
+                Mat4::IDENTITY,
+            );
+        }
+    }
+}
+
+/// Extra properties a level glTF node can be given, such as through custom
+/// properties in Blender.
+#[derive(Debug, Default, Deserialize)]
+struct NodeExtras {
+    /// Blueprint to spawn at the node.
+    #[serde(default)]
+    blueprint: Option<String>,
+
+    /// Kind of barrier the node's mesh stands for.
+    #[serde(default)]
+    barrier: Option<BarrierTag>,
+}
+
+/// Kinds of barrier meshes that can be tagged in a level glTF.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum BarrierTag {
+    Wall,
+    Glass,
+    Grating,
+}
+
+impl BarrierTag {
+    fn blueprint_name(self) -> &'static str {
+        match self {
+            Self::Wall => "wall.blueprint",
+            Self::Glass => "glass.blueprint",
+            Self::Grating => "grating.blueprint",
+        }
+    }
+}
+
+/// Spawns entities for a level glTF node and its children.
+///
+/// Nodes that have meshes keep them as visuals, so barriers should be modeled
+/// as unit cubes that get sized by their node's scale.
+fn spawn_entities_from_nodes(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    node: &GltfNode,
+    parent_matrix: Mat4,
+) {
+    let matrix = parent_matrix * node.transform.compute_matrix();
+
+    if let Some(blueprint_name) = node_blueprint_name(game_assets, node) {
+        let mut spawn = SpawnEntityFromBlueprint::new(blueprint_name, matrix);
+
+        if let Some(mesh) = &node.mesh {
+            spawn = spawn.with_mesh(mesh.clone());
+        }
+
+        commands.trigger(spawn);
+    }
+
+    for child in &node.children {
+        spawn_entities_from_nodes(commands, game_assets, child, matrix);
+    }
+}
+
+/// Picks the blueprint a level glTF node stands for, preferring its extras
+/// over its name.
+///
+/// Names are matched without Blender's duplicate suffixes, so a node named
+/// `guard.003` spawns `guard.blueprint`. Nodes that don't match a blueprint
+/// are treated as plain geometry.
+fn node_blueprint_name(
+    game_assets: &GameAssets,
+    node: &GltfNode,
+) -> Option<String> {
+    let extras = node
+        .extras
+        .as_ref()
+        .and_then(|extras| {
+            serde_json::from_str::<NodeExtras>(&extras.value)
+                .map_err(|error| {
+                    warn!(
+                        "Invalid extras on glTF node `{}`: {error}",
+                        node.name
+                    )
+                })
+                .ok()
+        })
+        .unwrap_or_default();
+    let tagged_blueprint_name = extras.blueprint.or_else(|| {
+        extras
+            .barrier
+            .map(|barrier| barrier.blueprint_name().to_string())
+    });
+
+    if let Some(blueprint_name) = tagged_blueprint_name {
+        if game_assets.blueprints.contains_key(blueprint_name.as_str()) {
+            return Some(blueprint_name);
+        }
+
+        error!(
+            "Unknown blueprint `{blueprint_name}` on glTF node `{}`",
+            node.name
+        );
+        return None;
+    }
+
+    let stem = node.name.split('.').next().unwrap_or_default();
+    let blueprint_name = format!("{stem}.blueprint");
+
+    game_assets
+        .blueprints
+        .contains_key(blueprint_name.as_str())
+        .then_some(blueprint_name)
+}
@@ -1,20 +1,80 @@
 use bevy::{asset::*, prelude::*, reflect::TypePath};
 use bevy_common_assets::ron::RonAssetPlugin;
+use derive_new::new;
 use serde::Deserialize;
 
+use crate::prelude::*;
+
 pub(super) struct SoundWavesPlugin;
 
 impl Plugin for SoundWavesPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(RonAssetPlugin::<SoundWaveConfig>::new(&[
             "sound_wave.ron",
-        ]));
+        ]))
+        .observe(spawn_sound_wave);
+    }
+}
+
+/// Spawn an expanding sound wave from a point.
+#[derive(Event, new)]
+pub struct SpawnSoundWave {
+    sound_wave: Handle<SoundWaveConfig>,
+    origin: Vec3,
+
+    #[new(default)]
+    emitter: Option<Entity>,
+
+    #[new(value = "1.0")]
+    radius_scale: f32,
+}
+
+impl SpawnSoundWave {
+    /// Marks the entity responsible for making the sound.
+    pub fn with_emitter(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    /// Scales how far the sound wave travels.
+    pub fn with_radius_scale(mut self, radius_scale: f32) -> Self {
+        self.radius_scale = radius_scale;
+        self
     }
 }
 
-/// Configs for spawnable entities.
+/// Configs for spawnable sound waves.
 #[derive(Asset, Debug, Deserialize, Resource, TypePath)]
 pub struct SoundWaveConfig {
+    /// Hex color code used when drawing the sound wave.
     pub color: String,
+
+    /// How far the sound wave travels in `meters`.
+    pub radius: f32,
+
+    /// How fast the sound wave expands in `meters/second`.
+    pub speed: f32,
+
+    /// How long the sound wave lasts in `seconds`.
+    pub lifetime: f32,
     // sound??
 }
+
+fn spawn_sound_wave(
+    trigger: Trigger<SpawnSoundWave>,
+    mut commands: Commands,
+    sound_waves: Res<Assets<SoundWaveConfig>>,
+) {
+    let SpawnSoundWave {
+        sound_wave,
+        origin,
+        emitter,
+        radius_scale,
+    } = trigger.event();
+    let config = sound_waves.get(sound_wave).unwrap();
+
+    commands.spawn((
+        ForStates::new(GameState::IN_LEVEL),
+        SoundWave::new(config, *origin, config.radius * radius_scale, *emitter),
+    ));
+}
@@ -1,3 +1,4 @@
+use avian3d::prelude::*;
 use bevy::{
     ecs::{prelude::*, system::SystemParam},
     prelude::*,
@@ -8,6 +9,9 @@ use crate::prelude::*;
 
 const ANIMATION_TRANSITION_DELAY: Duration = Duration::from_millis(500);
 
+/// Distance a sound is pushed off a surface after bouncing.
+const BOUNCE_OFFSET: f32 = 0.01;
+
 /// Allows animations to easily be played on entities that support them.
 #[derive(SystemParam)]
 pub struct Animations<'w, 's> {
@@ -87,3 +91,169 @@ impl<'w, 's> Animations<'w, 's> {
         transitions.get_main_animation()
     }
 }
+
+/// Tests whether sight lines are obstructed by entities that block vision.
+#[derive(SystemParam)]
+pub struct LineOfSight<'w, 's> {
+    spatial_query: SpatialQuery<'w, 's>,
+    blocks_vision_query: Query<'w, 's, (), With<BlocksVision>>,
+}
+
+impl<'w, 's> LineOfSight<'w, 's> {
+    /// Checks that nothing blocking vision lies between two points.
+    pub fn is_clear(&self, start: Vec3, end: Vec3) -> bool {
+        let diff = end - start;
+        let Ok(direction) = Dir3::new(diff) else {
+            return true;
+        };
+
+        !self
+            .spatial_query
+            .ray_hits(
+                start,
+                direction,
+                diff.length(),
+                u32::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .iter()
+            .any(|hit| self.blocks_vision_query.contains(hit.entity))
+    }
+}
+
+/// Where a cast sound hit a wall and how the sound should react.
+#[derive(Clone, Copy, Debug)]
+pub struct SoundHit {
+    /// Distance traveled before hitting the wall in `meters`.
+    pub distance: f32,
+
+    /// Surface normal of the wall in world space.
+    pub normal: Vec3,
+
+    /// Whether the sound bounces off the wall rather than stopping.
+    pub deflects: bool,
+}
+
+impl SoundHit {
+    /// Reflects a sound off the wall at the point it hit, keeping it moving
+    /// along the ground.
+    ///
+    /// Returns the position pushed slightly off the wall and the new direction.
+    pub fn bounce(&self, position: Vec3, direction: Dir3) -> (Vec3, Dir3) {
+        let mut normal = self.normal.with_y(0.0).normalize_or_zero();
+
+        if normal.dot(*direction) > 0.0 {
+            normal = -normal;
+        }
+
+        let reflected = *direction - 2.0 * direction.dot(normal) * normal;
+
+        (
+            position + normal * BOUNCE_OFFSET,
+            Dir3::new(reflected).unwrap_or(-direction),
+        )
+    }
+}
+
+/// Casts sounds through the world against walls that block or deflect them.
+#[derive(SystemParam)]
+pub struct SoundBarriers<'w, 's> {
+    spatial_query: SpatialQuery<'w, 's>,
+    walls_query: Query<'w, 's, (Has<Barrier>, Has<DeflectsSounds>)>,
+}
+
+impl<'w, 's> SoundBarriers<'w, 's> {
+    /// Finds the nearest wall along a path that a sound would react to.
+    pub fn cast(
+        &self,
+        origin: Vec3,
+        direction: Dir3,
+        max_distance: f32,
+    ) -> Option<SoundHit> {
+        self.spatial_query
+            .ray_hits(
+                origin,
+                direction,
+                max_distance,
+                u32::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .into_iter()
+            .filter_map(|hit| {
+                let (is_barrier, deflects) =
+                    self.walls_query.get(hit.entity).ok()?;
+
+                (is_barrier || deflects).then_some(SoundHit {
+                    distance: hit.time_of_impact,
+                    normal: hit.normal,
+                    deflects,
+                })
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Checks that no wall a sound would react to lies between two points,
+    /// ignoring an entity such as the one at the end point.
+    pub fn is_clear(&self, start: Vec3, end: Vec3, ignored: Entity) -> bool {
+        let diff = end - start;
+        let Ok(direction) = Dir3::new(diff) else {
+            return true;
+        };
+
+        !self
+            .spatial_query
+            .ray_hits(
+                start,
+                direction,
+                diff.length(),
+                u32::MAX,
+                true,
+                SpatialQueryFilter::default().with_excluded_entities([ignored]),
+            )
+            .iter()
+            .any(|hit| {
+                self.walls_query
+                    .get(hit.entity)
+                    .is_ok_and(|(is_barrier, deflects)| is_barrier || deflects)
+            })
+    }
+
+    /// Traces the path a sound takes as it bounces off walls that deflect it,
+    /// until it travels its full distance, runs out of bounces, or stops at a
+    /// barrier.
+    pub fn trace_bounces(
+        &self,
+        origin: Vec3,
+        mut direction: Dir3,
+        max_distance: f32,
+        max_bounces: u32,
+    ) -> Vec<Vec3> {
+        let mut path = vec![origin];
+        let mut position = origin;
+        let mut remaining_distance = max_distance;
+        let mut bounces = 0;
+
+        while remaining_distance > 0.0 {
+            let Some(hit) = self.cast(position, direction, remaining_distance)
+            else {
+                path.push(position + direction * remaining_distance);
+                break;
+            };
+
+            position += direction * hit.distance;
+            remaining_distance -= hit.distance;
+            path.push(position);
+
+            if !hit.deflects || bounces == max_bounces {
+                break;
+            }
+
+            bounces += 1;
+            (position, direction) = hit.bounce(position, direction);
+        }
+
+        path
+    }
+}
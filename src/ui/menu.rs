@@ -22,13 +22,15 @@ impl Plugin for MenuPlugin {
 #[derive(Actionlike, Clone, Copy, Debug, Eq, Hash, PartialEq, Reflect)]
 pub enum MenuAction {
     Exit,
+    Restart,
 }
 
 impl MenuAction {
     fn make_input_map() -> InputMap<Self> {
         use MenuAction::*;
 
-        let input_map = InputMap::new([(Exit, KeyCode::Escape)]);
+        let input_map =
+            InputMap::new([(Exit, KeyCode::Escape), (Restart, KeyCode::KeyR)]);
 
         input_map
     }
@@ -36,12 +38,25 @@ impl MenuAction {
 
 fn handle_menu_inputs(
     menu_action_state: Res<ActionState<MenuAction>>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
     mut app_exit: EventWriter<AppExit>,
 ) {
     use MenuAction::*;
 
     if menu_action_state.just_pressed(&Exit) {
         app_exit.send_default();
+    } else if menu_action_state.just_pressed(&Restart)
+        && matches!(
+            game_state.get(),
+            GameState::Paused
+                | GameState::Gameplay
+                | GameState::GameOver
+                | GameState::CampaignComplete
+        )
+    {
+        next_game_state.set(GameState::LevelTransition);
+        info!("Restarted level");
     }
 }
 